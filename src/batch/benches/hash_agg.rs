@@ -48,6 +48,7 @@ fn create_agg_call(
         distinct: false,
         order_by: vec![],
         filter: None,
+        ignore_nulls: false,
         direct_args: vec![],
     }
 }
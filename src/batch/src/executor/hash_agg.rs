@@ -360,6 +360,7 @@ mod tests {
                 distinct: false,
                 order_by: vec![],
                 filter: None,
+                ignore_nulls: false,
                 direct_args: vec![],
             };
 
@@ -435,6 +436,7 @@ mod tests {
             distinct: false,
             order_by: vec![],
             filter: None,
+            ignore_nulls: false,
             direct_args: vec![],
         };
 
@@ -548,6 +550,7 @@ mod tests {
             distinct: false,
             order_by: vec![],
             filter: None,
+            ignore_nulls: false,
             direct_args: vec![],
         };
 
@@ -694,6 +694,26 @@ pub struct StorageConfig {
     #[serde(default = "default::storage::mem_table_spill_threshold")]
     pub mem_table_spill_threshold: usize,
 
+    /// Capacity of the channel for high-volume hummock events (e.g. uploading an
+    /// immutable memtable). Writers are backpressured when the event handler falls
+    /// this many events behind. Control events are not subject to this limit.
+    #[serde(default = "default::storage::hummock_event_channel_capacity")]
+    pub hummock_event_channel_capacity: usize,
+
+    /// Timeout in seconds for a pending epoch sync request. When positive, a sync
+    /// request that has been pending longer than this receives an error describing the
+    /// outstanding upload tasks instead of waiting forever. 0 disables the timeout.
+    #[serde(default = "default::storage::hummock_sync_timeout_sec")]
+    pub hummock_sync_timeout_sec: u64,
+
+    /// Maximum number of registered read version instances per table. Each instance
+    /// holds a reference to the table's read version data, so a runaway job
+    /// registering instances without destroying them leaks memory; registrations
+    /// beyond the cap are logged loudly to catch it early. The cap is well above any
+    /// normal parallelism and does not reject the registration.
+    #[serde(default = "default::storage::max_read_version_instances_per_table")]
+    pub max_read_version_instances_per_table: usize,
+
     #[serde(default)]
     pub object_store: ObjectStoreConfig,
 }
@@ -855,6 +875,12 @@ pub struct StreamingDeveloperConfig {
     #[serde(default = "default::developer::unsafe_stream_extreme_cache_size")]
     pub unsafe_extreme_cache_size: usize,
 
+    /// Limit in bytes of a serialized cache key in an extreme aggregation call. Entries
+    /// whose cache key exceeds the limit are not cached, falling back to state-table
+    /// reads for the group. Unlimited if not set.
+    #[serde(default)]
+    pub unsafe_extreme_cache_key_size_limit: Option<usize>,
+
     /// The maximum size of the chunk produced by executor at a time.
     #[serde(default = "default::developer::stream_chunk_size")]
     pub chunk_size: usize,
@@ -1299,6 +1325,23 @@ pub mod default {
             4 << 20
         }
 
+        pub fn hummock_event_channel_capacity() -> usize {
+            // Large enough that a healthy event handler never blocks writers, while
+            // still bounding memory when it is stuck (e.g. object store outage).
+            1024
+        }
+
+        pub fn hummock_sync_timeout_sec() -> u64 {
+            // Disabled by default to preserve the wait-forever semantics.
+            0
+        }
+
+        pub fn max_read_version_instances_per_table() -> usize {
+            // Far above the per-node parallelism of any healthy job, so only a
+            // registration leak can reach it.
+            4096
+        }
+
         pub fn compactor_fast_max_compact_delete_ratio() -> u32 {
             40
         }
@@ -1582,6 +1625,19 @@ pub mod default {
         pub fn enable_emergency_picker() -> bool {
             DEFAULT_EMERGENCY_PICKER
         }
+
+        pub fn compaction_mode() -> i32 {
+            // `CompactionMode::Range`
+            1
+        }
+
+        pub fn split_by_state_table() -> bool {
+            false
+        }
+
+        pub fn split_weight_by_vnode() -> u32 {
+            0
+        }
     }
 
     pub mod object_store_config {
@@ -1739,6 +1795,12 @@ pub struct CompactionConfig {
     pub tombstone_reclaim_ratio: u32,
     #[serde(default = "default::compaction_config::enable_emergency_picker")]
     pub enable_emergency_picker: bool,
+    #[serde(default = "default::compaction_config::compaction_mode")]
+    pub compaction_mode: i32,
+    #[serde(default = "default::compaction_config::split_by_state_table")]
+    pub split_by_state_table: bool,
+    #[serde(default = "default::compaction_config::split_weight_by_vnode")]
+    pub split_weight_by_vnode: u32,
 }
 
 #[cfg(test)]
@@ -143,6 +143,15 @@ pub struct ConfigMap {
     #[parameter(default = false)]
     streaming_enable_arrangement_backfill: bool,
 
+    /// Enable the aggressive mode of streaming filter expression simplification, which
+    /// folds the tautology `(NOT e) OR e` in a filter predicate to plain `true` instead
+    /// of the conservative `IS NOT NULL` wrapping of the involved column. The
+    /// conservative default preserves the three-valued-logic behavior of the original
+    /// predicate (a NULL predicate filters the row out); the aggressive mode keeps rows
+    /// whose column is NULL as well. Defaults to false.
+    #[parameter(default = false)]
+    streaming_filter_simplify_aggressive: bool,
+
     /// Allow `jsonb` in stream key
     #[parameter(default = false, rename = "rw_streaming_allow_jsonb_in_stream_key")]
     streaming_allow_jsonb_in_stream_key: bool,
@@ -18,8 +18,12 @@ use std::hash::Hash;
 use bytes::Buf;
 use jsonbb::{Value, ValueRef};
 
+use crate::array::{ListValue, StructRef, StructValue};
 use crate::estimate_size::EstimateSize;
-use crate::types::{Scalar, ScalarRef};
+use crate::types::{
+    DataType, Datum, Scalar, ScalarImpl, ScalarRef, ScalarRefImpl, StructType, ToOwnedDatum, F64,
+};
+use crate::util::iter_util::ZipEqDebug;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct JsonbVal(pub(crate) Value);
@@ -384,6 +388,193 @@ impl<'a> JsonbRef<'a> {
     pub fn capacity(self) -> usize {
         self.0.capacity()
     }
+
+    /// Convert the jsonb value to a datum of the specified type.
+    pub fn to_datum(self, ty: &DataType) -> Result<Datum, String> {
+        if !matches!(
+            ty,
+            DataType::Jsonb
+                | DataType::Boolean
+                | DataType::Int16
+                | DataType::Int32
+                | DataType::Int64
+                | DataType::Float32
+                | DataType::Float64
+                | DataType::Decimal
+                | DataType::Varchar
+                | DataType::List(_)
+                | DataType::Struct(_)
+        ) {
+            return Err(format!("cannot cast jsonb to {ty}"));
+        }
+        if self.0.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(match ty {
+            DataType::Jsonb => ScalarImpl::Jsonb(self.to_owned_scalar()),
+            DataType::Boolean => ScalarImpl::Bool(self.as_bool()?),
+            DataType::Int16 => ScalarImpl::Int16(self.as_cast_number()?),
+            DataType::Int32 => ScalarImpl::Int32(self.as_cast_number()?),
+            DataType::Int64 => ScalarImpl::Int64(self.as_cast_number()?),
+            DataType::Float32 => ScalarImpl::Float32(self.as_cast_number()?),
+            DataType::Float64 => ScalarImpl::Float64(self.as_number()?.into()),
+            DataType::Decimal => ScalarImpl::Decimal(self.as_cast_number()?),
+            DataType::Varchar => ScalarImpl::Utf8(self.force_string().into()),
+            DataType::List(t) => ScalarImpl::List(self.to_list(t)?),
+            DataType::Struct(s) => ScalarImpl::Struct(self.to_struct(s)?),
+            _ => unreachable!(),
+        }))
+    }
+
+    /// Read the jsonb as a JSON number and convert it to the target numeric type.
+    fn as_cast_number<T: TryFrom<F64>>(&self) -> Result<T, String> {
+        F64::from(self.as_number()?)
+            .try_into()
+            .map_err(|_| "jsonb number out of range".to_string())
+    }
+
+    /// Convert the jsonb value to a list of the specified element type.
+    ///
+    /// Elements are converted recursively via [`Self::to_datum`], so a JSON array of
+    /// objects maps to a `struct[]` by converting each object to an element struct.
+    pub fn to_list(self, elem_type: &DataType) -> Result<ListValue, String> {
+        let array = self
+            .0
+            .as_array()
+            .ok_or_else(|| format!("expected JSON array, but found a jsonb {}", self.type_name()))?;
+        let mut builder = elem_type.create_array_builder(array.len());
+        for v in array.iter() {
+            builder.append(Self(v).to_datum(elem_type)?);
+        }
+        Ok(ListValue::new(builder.finish()))
+    }
+
+    /// Convert the jsonb value to a struct of the specified type, matching JSON object
+    /// keys to struct field names. Fields absent from the JSON object become NULL.
+    pub fn to_struct(self, ty: &StructType) -> Result<StructValue, String> {
+        let object = self.0.as_object().ok_or_else(|| {
+            format!(
+                "cannot call populate_composite on a jsonb {}",
+                self.type_name()
+            )
+        })?;
+        let mut fields = Vec::with_capacity(ty.len());
+        for (name, ty) in ty.iter() {
+            let datum = match object.get(name) {
+                Some(v) => Self(v).to_datum(ty)?,
+                None => None,
+            };
+            fields.push(datum);
+        }
+        Ok(StructValue::new(fields))
+    }
+
+    /// Like [`Self::to_struct`], but fields absent from the JSON object take their value
+    /// from the corresponding field of the `base` row instead of becoming NULL.
+    ///
+    /// Nested struct fields are populated recursively: an inner field absent from the
+    /// JSON object defaults to the matching field of the nested `base` value, not NULL.
+    pub fn populate_struct(
+        self,
+        ty: &StructType,
+        base: Option<StructRef<'_>>,
+    ) -> Result<StructValue, String> {
+        let Some(base) = base else {
+            return self.to_struct(ty);
+        };
+        let object = self.0.as_object().ok_or_else(|| {
+            format!(
+                "cannot call populate_composite on a jsonb {}",
+                self.type_name()
+            )
+        })?;
+        let mut fields = Vec::with_capacity(ty.len());
+        for ((name, ty), base_field) in ty.iter().zip_eq_debug(base.iter_fields_ref()) {
+            let datum = match object.get(name) {
+                Some(v) => match ty {
+                    // Recurse into nested structs so that the base row provides
+                    // defaults at every level, as in PostgreSQL. A JSON `null`
+                    // still overwrites the whole nested value with NULL.
+                    DataType::Struct(nested) if !v.is_null() => {
+                        let nested_base = match base_field {
+                            Some(ScalarRefImpl::Struct(b)) => Some(b),
+                            _ => None,
+                        };
+                        Some(ScalarImpl::Struct(
+                            Self(v).populate_struct(nested, nested_base)?,
+                        ))
+                    }
+                    _ => Self(v).to_datum(ty)?,
+                },
+                None => base_field.to_owned_datum(),
+            };
+            fields.push(datum);
+        }
+        Ok(StructValue::new(fields))
+    }
+
+    /// Like [`Self::populate_struct`], but when no JSON key matches a field name
+    /// exactly, keys are compared case-insensitively, following PostgreSQL's downcasing
+    /// of unquoted identifiers. It is an error if two JSON keys differing only by case
+    /// both match a field.
+    pub fn populate_struct_ci(
+        self,
+        ty: &StructType,
+        base: Option<StructRef<'_>>,
+    ) -> Result<StructValue, String> {
+        let object = self.0.as_object().ok_or_else(|| {
+            format!(
+                "cannot call populate_composite on a jsonb {}",
+                self.type_name()
+            )
+        })?;
+        let base_fields: Vec<Option<crate::types::DatumRef<'_>>> = match base {
+            Some(base) => base.iter_fields_ref().map(Some).collect(),
+            None => vec![None; ty.len()],
+        };
+        let mut fields = Vec::with_capacity(ty.len());
+        for ((name, ty), base_field) in ty.iter().zip_eq_debug(base_fields) {
+            let value = match object.get(name) {
+                Some(v) => Some(v),
+                None => {
+                    let mut found = None;
+                    for (key, v) in object.iter() {
+                        if key.eq_ignore_ascii_case(name) {
+                            if found.is_some() {
+                                return Err(format!(
+                                    "JSON field matching for \"{}\" is ambiguous: multiple keys differ only by case",
+                                    name
+                                ));
+                            }
+                            found = Some(v);
+                        }
+                    }
+                    found
+                }
+            };
+            let datum = match value {
+                Some(v) => match ty {
+                    // Recurse with the same case-insensitive matching, so nested
+                    // fields also take defaults from the base row and downcased
+                    // keys are found at every level. As in [`Self::populate_struct`],
+                    // a JSON `null` overwrites the whole nested value with NULL.
+                    DataType::Struct(nested) if !v.is_null() => {
+                        let nested_base = match base_field {
+                            Some(Some(ScalarRefImpl::Struct(b))) => Some(b),
+                            _ => None,
+                        };
+                        Some(ScalarImpl::Struct(
+                            Self(v).populate_struct_ci(nested, nested_base)?,
+                        ))
+                    }
+                    _ => Self(v).to_datum(ty)?,
+                },
+                None => base_field.and_then(|f| f.to_owned_datum()),
+            };
+            fields.push(datum);
+        }
+        Ok(StructValue::new(fields))
+    }
 }
 
 /// A custom implementation for [`serde_json::ser::Formatter`] to match PostgreSQL, which adds extra
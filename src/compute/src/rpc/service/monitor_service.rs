@@ -23,12 +23,14 @@ use risingwave_common::config::{MetricLevel, ServerConfig};
 use risingwave_common_heap_profiling::{AUTO_DUMP_SUFFIX, COLLAPSED_SUFFIX, MANUALLY_DUMP_SUFFIX};
 use risingwave_pb::monitor_service::monitor_service_server::MonitorService;
 use risingwave_pb::monitor_service::{
-    AnalyzeHeapRequest, AnalyzeHeapResponse, BackPressureInfo, GetBackPressureRequest,
-    GetBackPressureResponse, HeapProfilingRequest, HeapProfilingResponse, ListHeapProfilingRequest,
+    AnalyzeHeapRequest, AnalyzeHeapResponse, BackPressureInfo, DrainHummockEventsRequest,
+    DrainHummockEventsResponse, GetBackPressureRequest, GetBackPressureResponse,
+    HeapProfilingRequest, HeapProfilingResponse, ListHeapProfilingRequest,
     ListHeapProfilingResponse, ProfilingRequest, ProfilingResponse, StackTraceRequest,
     StackTraceResponse,
 };
 use risingwave_rpc_client::error::ToTonicStatus;
+use risingwave_storage::StateStoreImpl;
 use risingwave_stream::executor::monitor::global_streaming_metrics;
 use risingwave_stream::task::LocalStreamManager;
 use thiserror_ext::AsReport;
@@ -39,6 +41,7 @@ pub struct MonitorServiceImpl {
     stream_mgr: LocalStreamManager,
     grpc_await_tree_reg: Option<AwaitTreeRegistryRef>,
     server_config: ServerConfig,
+    state_store: StateStoreImpl,
 }
 
 impl MonitorServiceImpl {
@@ -46,11 +49,13 @@ impl MonitorServiceImpl {
         stream_mgr: LocalStreamManager,
         grpc_await_tree_reg: Option<AwaitTreeRegistryRef>,
         server_config: ServerConfig,
+        state_store: StateStoreImpl,
     ) -> Self {
         Self {
             stream_mgr,
             grpc_await_tree_reg,
             server_config,
+            state_store,
         }
     }
 }
@@ -264,6 +269,23 @@ impl MonitorService for MonitorServiceImpl {
             back_pressure_infos,
         }))
     }
+
+    #[cfg_attr(coverage, coverage(off))]
+    async fn drain_hummock_events(
+        &self,
+        _request: Request<DrainHummockEventsRequest>,
+    ) -> Result<Response<DrainHummockEventsResponse>, Status> {
+        let Some(hummock) = self.state_store.as_hummock() else {
+            return Err(Status::failed_precondition(
+                "the state store of the node is not hummock",
+            ));
+        };
+        hummock
+            .drain_pending_events()
+            .await
+            .map_err(|e| e.to_status(Code::Internal, "monitor"))?;
+        Ok(Response::new(DrainHummockEventsResponse {}))
+    }
 }
 
 pub use grpc_middleware::*;
@@ -345,7 +345,7 @@ pub async fn compute_node_serve(
         connector_params,
         stream_config,
         worker_id,
-        state_store,
+        state_store.clone(),
         dml_mgr,
         system_params_manager.clone(),
         source_metrics,
@@ -380,6 +380,7 @@ pub async fn compute_node_serve(
         stream_mgr.clone(),
         grpc_await_tree_reg.clone(),
         config.server.clone(),
+        state_store,
     );
     let config_srv = ConfigServiceImpl::new(batch_mgr, stream_mgr);
     let health_srv = HealthServiceImpl::new();
@@ -111,6 +111,163 @@ macro_rules! handle_data_type {
     }};
 }
 
+type BoxedError = Box<dyn std::error::Error + Sync + Send>;
+
+/// The text form of a `tsvector` or `tsquery` column.
+///
+/// RisingWave has no native full-text-search types, so these columns are decoded into
+/// their text representation for `Varchar` columns. The text form keeps all lexemes,
+/// positions and weights, but downstream ranking functions cannot be evaluated on it,
+/// so it is lossy for ranking purposes.
+struct TsTextual(String);
+
+impl<'a> tokio_postgres::types::FromSql<'a> for TsTextual {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, BoxedError> {
+        match *ty {
+            Type::TS_VECTOR => Ok(Self(decode_tsvector(raw)?)),
+            Type::TSQUERY => Ok(Self(decode_tsquery(raw)?)),
+            _ => Err(format!("unexpected type {} for TsTextual", ty).into()),
+        }
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::TS_VECTOR | Type::TSQUERY)
+    }
+}
+
+fn read_bytes<'a>(buf: &mut &'a [u8], n: usize) -> Result<&'a [u8], BoxedError> {
+    if buf.len() < n {
+        return Err("unexpected end of message".into());
+    }
+    let (head, tail) = buf.split_at(n);
+    *buf = tail;
+    Ok(head)
+}
+
+fn read_u8(buf: &mut &[u8]) -> Result<u8, BoxedError> {
+    Ok(read_bytes(buf, 1)?[0])
+}
+
+fn read_u16(buf: &mut &[u8]) -> Result<u16, BoxedError> {
+    Ok(u16::from_be_bytes(read_bytes(buf, 2)?.try_into().unwrap()))
+}
+
+fn read_i32(buf: &mut &[u8]) -> Result<i32, BoxedError> {
+    Ok(i32::from_be_bytes(read_bytes(buf, 4)?.try_into().unwrap()))
+}
+
+fn read_cstr(buf: &mut &[u8]) -> Result<String, BoxedError> {
+    let end = buf
+        .iter()
+        .position(|&b| b == 0)
+        .ok_or("unterminated string in message")?;
+    let s = std::str::from_utf8(&buf[..end])?.to_string();
+    *buf = &buf[end + 1..];
+    Ok(s)
+}
+
+/// Quotes a lexeme for the tsvector/tsquery text form, doubling embedded quotes and
+/// backslashes.
+fn quote_lexeme(lexeme: &str) -> String {
+    format!("'{}'", lexeme.replace('\\', "\\\\").replace('\'', "''"))
+}
+
+fn weight_letter(weight: u16) -> Option<char> {
+    match weight {
+        3 => Some('A'),
+        2 => Some('B'),
+        1 => Some('C'),
+        // The default weight `D` is not printed.
+        _ => None,
+    }
+}
+
+/// Decodes the binary wire format of a `tsvector` (see `tsvector_send` in Postgres)
+/// into its text form, e.g. `'cat':1A 'fat'`.
+fn decode_tsvector(mut buf: &[u8]) -> Result<String, BoxedError> {
+    let size = read_i32(&mut buf)?;
+    let mut out = String::new();
+    for i in 0..size {
+        if i > 0 {
+            out.push(' ');
+        }
+        let lexeme = read_cstr(&mut buf)?;
+        out.push_str(&quote_lexeme(&lexeme));
+        let npos = read_u16(&mut buf)?;
+        for j in 0..npos {
+            out.push(if j == 0 { ':' } else { ',' });
+            let word_entry = read_u16(&mut buf)?;
+            // The two most significant bits are the weight, the rest the position.
+            out.push_str(&(word_entry & 0x3fff).to_string());
+            if let Some(weight) = weight_letter(word_entry >> 14) {
+                out.push(weight);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Decodes the binary wire format of a `tsquery` (see `tsquery_send` in Postgres) into
+/// its text form. Binary operators are always parenthesized, so the output may differ
+/// from the canonical Postgres output, but parses back to the same query.
+fn decode_tsquery(mut buf: &[u8]) -> Result<String, BoxedError> {
+    let _count = read_i32(&mut buf)?;
+    decode_tsquery_node(&mut buf)
+}
+
+fn decode_tsquery_node(buf: &mut &[u8]) -> Result<String, BoxedError> {
+    const QI_VAL: u8 = 1;
+    const QI_OPR: u8 = 2;
+    const OP_NOT: u8 = 1;
+    const OP_AND: u8 = 2;
+    const OP_OR: u8 = 3;
+    const OP_PHRASE: u8 = 4;
+
+    match read_u8(buf)? {
+        QI_VAL => {
+            let weights = read_u8(buf)?;
+            let prefix = read_u8(buf)?;
+            let operand = read_cstr(buf)?;
+            let mut out = quote_lexeme(&operand);
+            if weights != 0 || prefix != 0 {
+                out.push(':');
+                if prefix != 0 {
+                    out.push('*');
+                }
+                for (bit, letter) in [(3, 'A'), (2, 'B'), (1, 'C'), (0, 'D')] {
+                    if weights & (1 << bit) != 0 {
+                        out.push(letter);
+                    }
+                }
+            }
+            Ok(out)
+        }
+        QI_OPR => {
+            let oper = read_u8(buf)?;
+            if oper == OP_NOT {
+                return Ok(format!("!( {} )", decode_tsquery_node(buf)?));
+            }
+            let distance = if oper == OP_PHRASE {
+                read_u16(buf)?
+            } else {
+                0
+            };
+            // The right operand is stored before the left one.
+            let right = decode_tsquery_node(buf)?;
+            let left = decode_tsquery_node(buf)?;
+            let op = match oper {
+                OP_AND => "&".to_string(),
+                OP_OR => "|".to_string(),
+                OP_PHRASE if distance == 1 => "<->".to_string(),
+                OP_PHRASE => format!("<{}>", distance),
+                _ => return Err(format!("unrecognized tsquery operator {}", oper).into()),
+            };
+            Ok(format!("( {} {} {} )", left, op, right))
+        }
+        item_type => Err(format!("unrecognized tsquery item type {}", item_type).into()),
+    }
+}
+
 pub fn postgres_row_to_owned_row(row: tokio_postgres::Row, schema: &Schema) -> OwnedRow {
     let mut datums = vec![];
     for i in 0..schema.fields.len() {
@@ -159,6 +316,25 @@ pub fn postgres_row_to_owned_row(row: tokio_postgres::Row, schema: &Schema) -> O
                                 }
                             }
                         }
+                        // Full-text-search columns have no native equivalent, adapt
+                        // them to a VARCHAR column holding their text form.
+                        &Type::TS_VECTOR | &Type::TSQUERY => {
+                            let res = row.try_get::<_, Option<TsTextual>>(i);
+                            match res {
+                                Ok(val) => val.map(|v| ScalarImpl::from(v.0)),
+                                Err(err) => {
+                                    if let Ok(suppressed_count) = LOG_SUPPERSSER.check() {
+                                        tracing::error!(
+                                            suppressed_count,
+                                            column = name,
+                                            error = %err.as_report(),
+                                            "parse tsvector/tsquery column failed",
+                                        );
+                                    }
+                                    None
+                                }
+                            }
+                        }
                         _ => {
                             handle_data_type!(row, i, name, String)
                         }
@@ -313,3 +489,74 @@ pub fn postgres_row_to_owned_row(row: tokio_postgres::Row, schema: &Schema) -> O
     }
     OwnedRow::new(datums)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_tsquery, decode_tsvector};
+
+    fn push_cstr(buf: &mut Vec<u8>, s: &str) {
+        buf.extend_from_slice(s.as_bytes());
+        buf.push(0);
+    }
+
+    #[test]
+    fn test_decode_tsvector() {
+        // `to_tsvector('a fat cat')` as produced by `tsvector_send`.
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&2i32.to_be_bytes());
+        push_cstr(&mut buf, "cat");
+        buf.extend_from_slice(&2u16.to_be_bytes());
+        // Position 3 with weight A, position 7 with the default weight D.
+        buf.extend_from_slice(&((3u16 << 14) | 3).to_be_bytes());
+        buf.extend_from_slice(&7u16.to_be_bytes());
+        push_cstr(&mut buf, "fat");
+        buf.extend_from_slice(&0u16.to_be_bytes());
+
+        assert_eq!(decode_tsvector(&buf).unwrap(), "'cat':3A,7 'fat'");
+    }
+
+    #[test]
+    fn test_decode_tsquery() {
+        // `'fat' & 'ca':*` as produced by `tsquery_send`: the operator comes first,
+        // followed by the right operand, then the left one.
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&3i32.to_be_bytes());
+        buf.push(2); // QI_OPR
+        buf.push(2); // OP_AND
+        buf.push(1); // QI_VAL
+        buf.push(0); // no weights
+        buf.push(1); // prefix
+        push_cstr(&mut buf, "ca");
+        buf.push(1); // QI_VAL
+        buf.push(1 << 3); // weight A
+        buf.push(0);
+        push_cstr(&mut buf, "fat");
+
+        assert_eq!(decode_tsquery(&buf).unwrap(), "( 'fat':A & 'ca':* )");
+    }
+
+    #[test]
+    fn test_decode_tsquery_phrase_and_not() {
+        // `!'a' <2> 'b'`
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&4i32.to_be_bytes());
+        buf.push(2); // QI_OPR
+        buf.push(4); // OP_PHRASE
+        buf.extend_from_slice(&2u16.to_be_bytes());
+        buf.push(1); // QI_VAL
+        buf.push(0);
+        buf.push(0);
+        push_cstr(&mut buf, "b");
+        buf.push(2); // QI_OPR
+        buf.push(1); // OP_NOT
+        buf.push(1); // QI_VAL
+        buf.push(0);
+        buf.push(0);
+        push_cstr(&mut buf, "a");
+
+        assert_eq!(
+            decode_tsquery(&buf).unwrap(),
+            "( !( 'a' ) <2> 'b' )"
+        );
+    }
+}
@@ -18,6 +18,7 @@ use futures::stream::BoxStream;
 use futures_async_stream::try_stream;
 use risingwave_common::row::OwnedRow;
 use risingwave_common::types::ScalarImpl;
+use risingwave_common::util::sort_util::OrderType;
 
 use crate::error::{ConnectorError, ConnectorResult};
 use crate::source::cdc::external::{
@@ -39,7 +40,7 @@ impl MockExternalTableReader {
     }
 
     pub fn get_cdc_offset_parser() -> CdcOffsetParseFunc {
-        Box::new(move |_| Ok(CdcOffset::MySql(MySqlOffset::default())))
+        Box::new(move |_| Ok(Some(CdcOffset::MySql(MySqlOffset::default()))))
     }
 
     #[try_stream(boxed, ok = OwnedRow, error = ConnectorError)]
@@ -111,6 +112,7 @@ impl ExternalTableReader for MockExternalTableReader {
         _table_name: SchemaTableName,
         _start_pk: Option<OwnedRow>,
         _primary_keys: Vec<String>,
+        _pk_order_types: Vec<OrderType>,
     ) -> BoxStream<'_, ConnectorResult<OwnedRow>> {
         self.snapshot_read_inner()
     }
@@ -30,8 +30,10 @@ use risingwave_common::catalog::{Schema, OFFSET_COLUMN_NAME};
 use risingwave_common::row::OwnedRow;
 use risingwave_common::types::DataType;
 use risingwave_common::util::iter_util::ZipEqFast;
+use risingwave_common::util::sort_util::OrderType;
 use serde_derive::{Deserialize, Serialize};
 
+use crate::deserialize_bool_from_string;
 use crate::error::{ConnectorError, ConnectorResult};
 use crate::parser::mysql_row_to_owned_row;
 use crate::source::cdc::external::mock_external_table::MockExternalTableReader;
@@ -202,21 +204,58 @@ impl MySqlOffset {
     }
 }
 
-pub type CdcOffsetParseFunc = Box<dyn Fn(&str) -> ConnectorResult<CdcOffset> + Send>;
+/// Parses a serialized Debezium offset into a [`CdcOffset`]. `Ok(None)` means the
+/// offset carries no position to advance to, e.g. a Postgres heartbeat offset without
+/// an LSN, and callers should keep their last known offset.
+pub type CdcOffsetParseFunc = Box<dyn Fn(&str) -> ConnectorResult<Option<CdcOffset>> + Send>;
 
 pub trait ExternalTableReader {
     fn get_normalized_table_name(&self, table_name: &SchemaTableName) -> String;
 
     async fn current_cdc_offset(&self) -> ConnectorResult<CdcOffset>;
 
+    /// Reads a consistent snapshot of the table, ordered by the primary key in the
+    /// given per-column directions, optionally resuming after `start_pk`.
     fn snapshot_read(
         &self,
         table_name: SchemaTableName,
         start_pk: Option<OwnedRow>,
         primary_keys: Vec<String>,
+        pk_order_types: Vec<OrderType>,
     ) -> BoxStream<'_, ConnectorResult<OwnedRow>>;
 }
 
+/// An item yielded by [`snapshot_read_with_completion`]: either a data row of the
+/// snapshot, or the terminal marker signaling that the snapshot has finished.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SnapshotReadItem {
+    Row(OwnedRow),
+    /// Emitted exactly once after the last data row (also for empty tables), carrying
+    /// the consistent offset to resume the CDC stream from.
+    Completed(CdcOffset),
+}
+
+/// Reads a consistent snapshot like [`ExternalTableReader::snapshot_read`], additionally
+/// yielding a terminal [`SnapshotReadItem::Completed`] marker after the last data row.
+///
+/// The resume offset is captured before the snapshot data is read, so changes that
+/// happen during the read are replayed when switching to the stream.
+#[try_stream(boxed, ok = SnapshotReadItem, error = ConnectorError)]
+pub async fn snapshot_read_with_completion<R: ExternalTableReader>(
+    reader: &R,
+    table_name: SchemaTableName,
+    start_pk: Option<OwnedRow>,
+    primary_keys: Vec<String>,
+    pk_order_types: Vec<OrderType>,
+) {
+    let offset = reader.current_cdc_offset().await?;
+    #[for_await]
+    for row in reader.snapshot_read(table_name, start_pk, primary_keys, pk_order_types) {
+        yield SnapshotReadItem::Row(row?);
+    }
+    yield SnapshotReadItem::Completed(offset);
+}
+
 #[derive(Debug)]
 pub enum ExternalTableReaderImpl {
     MySql(MySqlExternalTableReader),
@@ -233,6 +272,7 @@ pub struct MySqlExternalTableReader {
     conn: tokio::sync::Mutex<mysql_async::Conn>,
 }
 
+#[serde_with::serde_as]
 #[derive(Debug, Clone, Deserialize)]
 pub struct ExternalTableConfig {
     #[serde(rename = "hostname")]
@@ -246,6 +286,62 @@ pub struct ExternalTableConfig {
     pub schema: String,
     #[serde(rename = "table.name")]
     pub table: String,
+    /// Timeout for establishing the connection to the upstream database. Without it, a
+    /// misconfigured network would let the connect attempt hang indefinitely and block
+    /// source creation. Unset means no timeout.
+    #[serde(rename = "connect.timeout.seconds", default)]
+    #[serde_as(as = "Option<serde_with::DisplayFromStr>")]
+    pub connect_timeout_secs: Option<u64>,
+    /// Statement timeout applied to the upstream session after connecting, so a stuck
+    /// query fails instead of blocking forever. Unset means no timeout.
+    #[serde(rename = "statement.timeout.seconds", default)]
+    #[serde_as(as = "Option<serde_with::DisplayFromStr>")]
+    pub statement_timeout_secs: Option<u64>,
+    /// Optional read replica to serve snapshot reads, so that a large backfill doesn't
+    /// load the primary. CDC offsets (LSN/txid) are always read from the primary, as
+    /// they must align with the replication slot there. Before each snapshot read the
+    /// replica is required to have replayed past the offset captured on the primary,
+    /// otherwise the read is delayed until the replication lag is caught up.
+    #[serde(rename = "snapshot.hostname", default)]
+    pub snapshot_host: Option<String>,
+    /// Port of the read replica. Defaults to `port` when unset.
+    #[serde(rename = "snapshot.port", default)]
+    pub snapshot_port: Option<String>,
+    /// Optional SQL predicate appended (with `AND`) to the `WHERE` clause of snapshot
+    /// reads, to backfill only a subset of a huge upstream table, e.g. `tenant_id = 42`.
+    /// It composes with the generated primary-key resume filter. The fragment must be a
+    /// single predicate and may not contain a semicolon.
+    #[serde(rename = "snapshot.filter", default)]
+    pub snapshot_filter: Option<String>,
+    /// Whether to snapshot a table without a primary key by ordering and resuming on the
+    /// hidden `ctid` system column (Postgres only). This is opt-in because concurrent
+    /// updates and `VACUUM FULL` can move rows to new ctids, so rows moved while the
+    /// snapshot is running may be missed or read twice. It is only suitable for
+    /// append-only tables.
+    #[serde(
+        rename = "snapshot.use.ctid",
+        default,
+        deserialize_with = "deserialize_bool_from_string"
+    )]
+    pub use_ctid_for_pk_less_table: bool,
+    /// Whether a row that fails to be converted during a snapshot read is skipped
+    /// instead of aborting the whole snapshot (Postgres only). Skipped rows are counted
+    /// in the `source_cdc_snapshot_skipped_rows` metric and logged together with the key
+    /// of the last successfully read row. This is opt-in because a skipped row is
+    /// silently missing from the materialized table.
+    #[serde(
+        rename = "snapshot.error.tolerance",
+        default,
+        deserialize_with = "deserialize_bool_from_string"
+    )]
+    pub snapshot_error_tolerance: bool,
+    /// SCRAM channel-binding mode for Postgres connections: `disable`, `prefer`
+    /// (the `tokio-postgres` default) or `require`. `require` needs a TLS connection to
+    /// provide the binding data; since the reader currently connects without TLS, it is
+    /// rejected up front with a clear error instead of failing the SCRAM exchange with
+    /// an opaque authentication error.
+    #[serde(rename = "channel.binding", default)]
+    pub channel_binding: Option<String>,
 }
 
 impl ExternalTableReader for MySqlExternalTableReader {
@@ -276,12 +372,17 @@ impl ExternalTableReader for MySqlExternalTableReader {
         table_name: SchemaTableName,
         start_pk: Option<OwnedRow>,
         primary_keys: Vec<String>,
+        pk_order_types: Vec<OrderType>,
     ) -> BoxStream<'_, ConnectorResult<OwnedRow>> {
-        self.snapshot_read_inner(table_name, start_pk, primary_keys)
+        self.snapshot_read_inner(table_name, start_pk, None, primary_keys, pk_order_types)
     }
 }
 
 impl MySqlExternalTableReader {
+    /// Suffix of the named parameters bound to the end key of a range read, to keep them
+    /// apart from the resume-filter parameters named after the key columns themselves.
+    const END_PARAM_SUFFIX: &'static str = "_end";
+
     pub async fn new(
         with_properties: HashMap<String, String>,
         rw_schema: Schema,
@@ -317,24 +418,111 @@ impl MySqlExternalTableReader {
 
     pub fn get_cdc_offset_parser() -> CdcOffsetParseFunc {
         Box::new(move |offset| {
-            Ok(CdcOffset::MySql(MySqlOffset::parse_debezium_offset(
+            Ok(Some(CdcOffset::MySql(MySqlOffset::parse_debezium_offset(
                 offset,
-            )?))
+            )?)))
         })
     }
 
+    /// Like [`ExternalTableReader::snapshot_read`], but additionally bounded from above
+    /// (inclusive) by `end_pk`, so that several readers can snapshot one table in
+    /// parallel, each scanning a primary-key range produced by
+    /// [`Self::sample_split_boundaries`].
+    pub fn snapshot_read_range(
+        &self,
+        table_name: SchemaTableName,
+        start_pk: Option<OwnedRow>,
+        end_pk: Option<OwnedRow>,
+        primary_keys: Vec<String>,
+        pk_order_types: Vec<OrderType>,
+    ) -> BoxStream<'_, ConnectorResult<OwnedRow>> {
+        self.snapshot_read_inner(table_name, start_pk, end_pk, primary_keys, pk_order_types)
+    }
+
+    /// Sample `num_splits - 1` evenly spaced primary-key boundaries, splitting the table
+    /// into `num_splits` ranges of roughly equal row count. Together with
+    /// [`Self::snapshot_read_range`] this allows snapshotting a table in parallel. An
+    /// empty result means the table is too small to bother splitting and should be read
+    /// as a single range.
+    pub async fn sample_split_boundaries(
+        &self,
+        table_name: SchemaTableName,
+        primary_keys: Vec<String>,
+        pk_order_types: Vec<OrderType>,
+        num_splits: usize,
+    ) -> ConnectorResult<Vec<OwnedRow>> {
+        // With at most one split there is no boundary to sample; bail out before the
+        // chunk-size computation, which would divide by zero for `num_splits == 0`.
+        if num_splits <= 1 {
+            return Ok(vec![]);
+        }
+
+        let pk_schema = Schema::new(
+            primary_keys
+                .iter()
+                .map(|pk| {
+                    self.rw_schema
+                        .fields
+                        .iter()
+                        .find(|f| &f.name == pk)
+                        .cloned()
+                        .with_context(|| format!("primary key {} not found in schema", pk))
+                })
+                .try_collect::<_, _, anyhow::Error>()?,
+        );
+
+        let table = self.get_normalized_table_name(&table_name);
+        let pk_cols = primary_keys
+            .iter()
+            .map(|pk| Self::quote_column(pk))
+            .join(",");
+        let order_key = Self::order_key_expression(&primary_keys, &pk_order_types);
+
+        let mut conn = self.conn.lock().await;
+
+        let row_count = conn
+            .query_first::<u64, _>(format!("SELECT COUNT(*) FROM {}", table))
+            .await?
+            .unwrap_or(0);
+        let chunk_size = row_count / num_splits as u64;
+        if chunk_size == 0 {
+            return Ok(vec![]);
+        }
+
+        let mut boundaries = Vec::with_capacity(num_splits - 1);
+        for i in 1..num_splits as u64 {
+            let sql = format!(
+                "SELECT {} FROM {} ORDER BY {} LIMIT 1 OFFSET {}",
+                pk_cols,
+                table,
+                order_key,
+                i * chunk_size
+            );
+            if let Some(mut row) = conn.query_first::<mysql_async::Row, _>(sql).await? {
+                boundaries.push(mysql_row_to_owned_row(&mut row, &pk_schema));
+            }
+        }
+        Ok(boundaries)
+    }
+
     #[try_stream(boxed, ok = OwnedRow, error = ConnectorError)]
     async fn snapshot_read_inner(
         &self,
         table_name: SchemaTableName,
         start_pk_row: Option<OwnedRow>,
+        end_pk_row: Option<OwnedRow>,
         primary_keys: Vec<String>,
+        pk_order_types: Vec<OrderType>,
     ) {
-        let order_key = primary_keys
-            .iter()
-            .map(|col| Self::quote_column(col))
-            .join(",");
-        let sql = if start_pk_row.is_none() {
+        let order_key = Self::order_key_expression(&primary_keys, &pk_order_types);
+        let mut filter_exprs = vec![];
+        if start_pk_row.is_some() {
+            filter_exprs.push(Self::filter_expression(&primary_keys, &pk_order_types));
+        }
+        if end_pk_row.is_some() {
+            filter_exprs.push(Self::upper_bound_expression(&primary_keys, &pk_order_types));
+        }
+        let sql = if filter_exprs.is_empty() {
             format!(
                 "SELECT {} FROM {} ORDER BY {}",
                 self.field_names,
@@ -342,12 +530,11 @@ impl MySqlExternalTableReader {
                 order_key
             )
         } else {
-            let filter_expr = Self::filter_expression(&primary_keys);
             format!(
                 "SELECT {} FROM {} WHERE {} ORDER BY {}",
                 self.field_names,
                 self.get_normalized_table_name(&table_name),
-                filter_expr,
+                filter_exprs.join(" AND "),
                 order_key
             )
         };
@@ -357,7 +544,7 @@ impl MySqlExternalTableReader {
         // Set session timezone to UTC
         conn.exec_drop("SET time_zone = \"+00:00\"", ()).await?;
 
-        if start_pk_row.is_none() {
+        if start_pk_row.is_none() && end_pk_row.is_none() {
             let mut result_set = conn.query_iter(sql).await?;
             let rs_stream = result_set.stream::<mysql_async::Row>().await?;
             if let Some(rs_stream) = rs_stream {
@@ -375,39 +562,14 @@ impl MySqlExternalTableReader {
                 }
             }
         } else {
-            let field_map = self
-                .rw_schema
-                .fields
-                .iter()
-                .map(|f| (f.name.as_str(), f.data_type.clone()))
-                .collect::<HashMap<_, _>>();
-
-            // fill in start primary key params
-            let params: Vec<_> = primary_keys
-                .iter()
-                .zip_eq_fast(start_pk_row.unwrap().into_iter())
-                .map(|(pk, datum)| {
-                    if let Some(value) = datum {
-                        let ty = field_map.get(pk.as_str()).unwrap();
-                        let val = match ty {
-                            DataType::Boolean => Value::from(value.into_bool()),
-                            DataType::Int16 => Value::from(value.into_int16()),
-                            DataType::Int32 => Value::from(value.into_int32()),
-                            DataType::Int64 => Value::from(value.into_int64()),
-                            DataType::Float32 => Value::from(value.into_float32().into_inner()),
-                            DataType::Float64 => Value::from(value.into_float64().into_inner()),
-                            DataType::Varchar => Value::from(String::from(value.into_utf8())),
-                            DataType::Date => Value::from(value.into_date().0),
-                            DataType::Time => Value::from(value.into_time().0),
-                            DataType::Timestamp => Value::from(value.into_timestamp().0),
-                            _ => bail!("unsupported primary key data type: {}", ty),
-                        };
-                        ConnectorResult::Ok((pk.clone(), val))
-                    } else {
-                        bail!("primary key {} cannot be null", pk);
-                    }
-                })
-                .try_collect::<_, _, ConnectorError>()?;
+            // fill in the primary key params of the resume filter and the end bound
+            let mut params = vec![];
+            if let Some(pk_row) = start_pk_row {
+                params.extend(self.pk_params(&primary_keys, pk_row, "")?);
+            }
+            if let Some(pk_row) = end_pk_row {
+                params.extend(self.pk_params(&primary_keys, pk_row, Self::END_PARAM_SUFFIX)?);
+            }
 
             let rs_stream = sql
                 .with(Params::from(params))
@@ -429,32 +591,122 @@ impl MySqlExternalTableReader {
         };
     }
 
+    fn order_key_expression(primary_keys: &[String], pk_order_types: &[OrderType]) -> String {
+        primary_keys
+            .iter()
+            .zip_eq_fast(pk_order_types.iter())
+            .map(|(col, order_type)| {
+                if order_type.is_ascending() {
+                    Self::quote_column(col)
+                } else {
+                    format!("{} DESC", Self::quote_column(col))
+                }
+            })
+            .join(",")
+    }
+
+    /// Converts the primary key row into named query params, with the param names being
+    /// the key column names followed by `param_suffix`.
+    fn pk_params(
+        &self,
+        primary_keys: &[String],
+        pk_row: OwnedRow,
+        param_suffix: &str,
+    ) -> ConnectorResult<Vec<(String, Value)>> {
+        let field_map = self
+            .rw_schema
+            .fields
+            .iter()
+            .map(|f| (f.name.as_str(), f.data_type.clone()))
+            .collect::<HashMap<_, _>>();
+
+        primary_keys
+            .iter()
+            .zip_eq_fast(pk_row.into_iter())
+            .map(|(pk, datum)| {
+                if let Some(value) = datum {
+                    let ty = field_map.get(pk.as_str()).unwrap();
+                    let val = match ty {
+                        DataType::Boolean => Value::from(value.into_bool()),
+                        DataType::Int16 => Value::from(value.into_int16()),
+                        DataType::Int32 => Value::from(value.into_int32()),
+                        DataType::Int64 => Value::from(value.into_int64()),
+                        DataType::Float32 => Value::from(value.into_float32().into_inner()),
+                        DataType::Float64 => Value::from(value.into_float64().into_inner()),
+                        DataType::Varchar => Value::from(String::from(value.into_utf8())),
+                        DataType::Date => Value::from(value.into_date().0),
+                        DataType::Time => Value::from(value.into_time().0),
+                        DataType::Timestamp => Value::from(value.into_timestamp().0),
+                        _ => bail!("unsupported primary key data type: {}", ty),
+                    };
+                    ConnectorResult::Ok((format!("{}{}", pk, param_suffix), val))
+                } else {
+                    bail!("primary key {} cannot be null", pk);
+                }
+            })
+            .try_collect::<_, _, ConnectorError>()
+    }
+
     // mysql cannot leverage the given key to narrow down the range of scan,
     // we need to rewrite the comparison conditions by our own.
     // (a, b) > (x, y) => (`a` > x) OR ((`a` = x) AND (`b` > y))
-    fn filter_expression(columns: &[String]) -> String {
+    // A descending key column compares with '<' instead, so the traversal continues
+    // towards smaller values of that column.
+    fn filter_expression(columns: &[String], order_types: &[OrderType]) -> String {
+        Self::filter_expression_with_suffix(columns, order_types, "")
+    }
+
+    /// Bounds a split range from above: rows at or before the end key in the scan order,
+    /// i.e. the negation of the resume filter evaluated against the end-key params.
+    fn upper_bound_expression(columns: &[String], order_types: &[OrderType]) -> String {
+        format!(
+            "NOT ({})",
+            Self::filter_expression_with_suffix(columns, order_types, Self::END_PARAM_SUFFIX)
+        )
+    }
+
+    fn filter_expression_with_suffix(
+        columns: &[String],
+        order_types: &[OrderType],
+        param_suffix: &str,
+    ) -> String {
+        let op = |i: usize| if order_types[i].is_ascending() { ">" } else { "<" };
         let mut conditions = vec![];
         // push the first condition
         conditions.push(format!(
-            "({} > :{})",
+            "({} {} :{}{})",
             Self::quote_column(&columns[0]),
-            columns[0]
+            op(0),
+            columns[0],
+            param_suffix
         ));
         for i in 2..=columns.len() {
             // '=' condition
             let mut condition = String::new();
             for (j, col) in columns.iter().enumerate().take(i - 1) {
                 if j == 0 {
-                    condition.push_str(&format!("({} = :{})", Self::quote_column(col), col));
+                    condition.push_str(&format!(
+                        "({} = :{}{})",
+                        Self::quote_column(col),
+                        col,
+                        param_suffix
+                    ));
                 } else {
-                    condition.push_str(&format!(" AND ({} = :{})", Self::quote_column(col), col));
+                    condition.push_str(&format!(
+                        " AND ({} = :{}{})",
+                        Self::quote_column(col),
+                        col,
+                        param_suffix
+                    ));
                 }
             }
-            // '>' condition
+            // comparison condition
             condition.push_str(&format!(
-                " AND ({} > :{})",
+                " AND ({} {} :{}{})",
                 Self::quote_column(&columns[i - 1]),
-                columns[i - 1]
+                op(i - 1),
+                columns[i - 1],
+                param_suffix
             ));
             conditions.push(format!("({})", condition));
         }
@@ -494,8 +746,9 @@ impl ExternalTableReader for ExternalTableReaderImpl {
         table_name: SchemaTableName,
         start_pk: Option<OwnedRow>,
         primary_keys: Vec<String>,
+        pk_order_types: Vec<OrderType>,
     ) -> BoxStream<'_, ConnectorResult<OwnedRow>> {
-        self.snapshot_read_inner(table_name, start_pk, primary_keys)
+        self.snapshot_read_inner(table_name, start_pk, primary_keys, pk_order_types)
     }
 }
 
@@ -516,16 +769,17 @@ impl ExternalTableReaderImpl {
         table_name: SchemaTableName,
         start_pk: Option<OwnedRow>,
         primary_keys: Vec<String>,
+        pk_order_types: Vec<OrderType>,
     ) {
         let stream = match self {
             ExternalTableReaderImpl::MySql(mysql) => {
-                mysql.snapshot_read(table_name, start_pk, primary_keys)
+                mysql.snapshot_read(table_name, start_pk, primary_keys, pk_order_types)
             }
             ExternalTableReaderImpl::Postgres(postgres) => {
-                postgres.snapshot_read(table_name, start_pk, primary_keys)
+                postgres.snapshot_read(table_name, start_pk, primary_keys, pk_order_types)
             }
             ExternalTableReaderImpl::Mock(mock) => {
-                mock.snapshot_read(table_name, start_pk, primary_keys)
+                mock.snapshot_read(table_name, start_pk, primary_keys, pk_order_types)
             }
         };
 
@@ -546,6 +800,7 @@ mod tests {
     use maplit::{convert_args, hashmap};
     use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, Schema};
     use risingwave_common::types::DataType;
+    use risingwave_common::util::sort_util::OrderType;
 
     use crate::source::cdc::external::{
         CdcOffset, ExternalTableReader, MySqlExternalTableReader, MySqlOffset, SchemaTableName,
@@ -554,15 +809,119 @@ mod tests {
     #[test]
     fn test_mysql_filter_expr() {
         let cols = vec!["id".to_string()];
-        let expr = MySqlExternalTableReader::filter_expression(&cols);
+        let expr = MySqlExternalTableReader::filter_expression(&cols, &[OrderType::ascending()]);
         assert_eq!(expr, "(`id` > :id)");
 
         let cols = vec!["aa".to_string(), "bb".to_string(), "cc".to_string()];
-        let expr = MySqlExternalTableReader::filter_expression(&cols);
+        let expr = MySqlExternalTableReader::filter_expression(
+            &cols,
+            &[
+                OrderType::ascending(),
+                OrderType::ascending(),
+                OrderType::ascending(),
+            ],
+        );
         assert_eq!(
             expr,
             "(`aa` > :aa) OR ((`aa` = :aa) AND (`bb` > :bb)) OR ((`aa` = :aa) AND (`bb` = :bb) AND (`cc` > :cc))"
         );
+
+        // A descending key column resumes towards smaller values.
+        let cols = vec!["aa".to_string(), "bb".to_string()];
+        let expr = MySqlExternalTableReader::filter_expression(
+            &cols,
+            &[OrderType::ascending(), OrderType::descending()],
+        );
+        assert_eq!(
+            expr,
+            "(`aa` > :aa) OR ((`aa` = :aa) AND (`bb` < :bb))"
+        );
+    }
+
+    #[test]
+    fn test_mysql_upper_bound_expr() {
+        let cols = vec!["id".to_string()];
+        let expr =
+            MySqlExternalTableReader::upper_bound_expression(&cols, &[OrderType::ascending()]);
+        assert_eq!(expr, "NOT ((`id` > :id_end))");
+
+        let cols = vec!["aa".to_string(), "bb".to_string()];
+        let expr = MySqlExternalTableReader::upper_bound_expression(
+            &cols,
+            &[OrderType::ascending(), OrderType::ascending()],
+        );
+        assert_eq!(
+            expr,
+            "NOT ((`aa` > :aa_end) OR ((`aa` = :aa_end) AND (`bb` > :bb_end)))"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_read_with_completion() {
+        use futures::StreamExt;
+
+        use crate::source::cdc::external::mock_external_table::MockExternalTableReader;
+        use crate::source::cdc::external::{snapshot_read_with_completion, SnapshotReadItem};
+
+        let watermark = MySqlOffset::new("binlog.000001".to_string(), 4);
+        let reader = MockExternalTableReader::new(vec![watermark.clone()]);
+        let table_name = SchemaTableName {
+            schema_name: "mock".to_string(),
+            table_name: "mock_table".to_string(),
+        };
+
+        let stream = snapshot_read_with_completion(
+            &reader,
+            table_name.clone(),
+            None,
+            vec!["id".to_string()],
+            vec![OrderType::ascending()],
+        );
+        let items: Vec<_> = stream.map(|item| item.unwrap()).collect().await;
+
+        // The terminal marker appears exactly once, after the last data row, with the
+        // offset captured at the start of the snapshot.
+        assert!(!items.is_empty());
+        assert!(items[..items.len() - 1]
+            .iter()
+            .all(|item| matches!(item, SnapshotReadItem::Row(_))));
+        assert_eq!(
+            *items.last().unwrap(),
+            SnapshotReadItem::Completed(CdcOffset::MySql(watermark))
+        );
+
+        // Exhaust the mock's snapshots, then read an empty table: the marker is still
+        // emitted exactly once.
+        let _ = snapshot_read_with_completion(
+            &reader,
+            table_name.clone(),
+            None,
+            vec!["id".to_string()],
+            vec![OrderType::ascending()],
+        )
+        .collect::<Vec<_>>()
+        .await;
+        let items: Vec<_> = snapshot_read_with_completion(
+            &reader,
+            table_name,
+            None,
+            vec!["id".to_string()],
+            vec![OrderType::ascending()],
+        )
+        .map(|item| item.unwrap())
+        .collect()
+        .await;
+        assert_eq!(
+            items
+                .iter()
+                .filter(|item| matches!(item, SnapshotReadItem::Completed(_)))
+                .count(),
+            1
+        );
+        assert!(matches!(
+            items.last().unwrap(),
+            SnapshotReadItem::Completed(_)
+        ));
     }
 
     #[test]
@@ -621,7 +980,12 @@ mod tests {
             table_name: "t1".to_string(),
         };
 
-        let stream = reader.snapshot_read(table_name, None, vec!["v1".to_string()]);
+        let stream = reader.snapshot_read(
+            table_name,
+            None,
+            vec!["v1".to_string()],
+            vec![OrderType::ascending()],
+        );
         pin_mut!(stream);
         #[for_await]
         for row in stream {
@@ -69,6 +69,19 @@ pub struct SourceMetrics {
     pub connector_source_rows_received: GenericCounterVec<AtomicU64>,
 
     pub direct_cdc_event_lag_latency: HistogramVec,
+
+    /// Total number of rows read by CDC snapshot (backfill) per upstream table.
+    pub cdc_snapshot_read_rows: GenericCounterVec<AtomicU64>,
+    /// Current snapshot position per upstream table, reported as the first primary-key
+    /// column of the last emitted row when it is an integer. Non-integer keys are not
+    /// reported.
+    pub cdc_snapshot_read_position: GenericGaugeVec<AtomicI64>,
+    /// Number of times a CDC snapshot was restarted from a saved position, e.g. due to
+    /// reconnection.
+    pub cdc_snapshot_restart_count: GenericCounterVec<AtomicU64>,
+    /// Number of rows skipped by a CDC snapshot because they failed to be converted and
+    /// `snapshot.error.tolerance` is enabled, per upstream table.
+    pub cdc_snapshot_skipped_rows: GenericCounterVec<AtomicU64>,
 }
 
 pub static GLOBAL_SOURCE_METRICS: LazyLock<SourceMetrics> =
@@ -126,6 +139,35 @@ impl SourceMetrics {
         let direct_cdc_event_lag_latency =
             register_histogram_vec_with_registry!(opts, &["table_name"], registry).unwrap();
 
+        let cdc_snapshot_read_rows = register_int_counter_vec_with_registry!(
+            "source_cdc_snapshot_read_rows",
+            "Total number of rows read by CDC snapshot per upstream table",
+            &["schema_name", "table_name"],
+            registry
+        )
+        .unwrap();
+        let cdc_snapshot_read_position = register_int_gauge_vec_with_registry!(
+            "source_cdc_snapshot_read_position",
+            "Current CDC snapshot position per upstream table, the first primary-key column of the last emitted row when it is an integer",
+            &["schema_name", "table_name"],
+            registry
+        )
+        .unwrap();
+        let cdc_snapshot_restart_count = register_int_counter_vec_with_registry!(
+            "source_cdc_snapshot_restart_count",
+            "Number of times a CDC snapshot was restarted from a saved position",
+            &["schema_name", "table_name"],
+            registry
+        )
+        .unwrap();
+        let cdc_snapshot_skipped_rows = register_int_counter_vec_with_registry!(
+            "source_cdc_snapshot_skipped_rows",
+            "Number of rows skipped by CDC snapshot due to conversion errors per upstream table",
+            &["schema_name", "table_name"],
+            registry
+        )
+        .unwrap();
+
         let rdkafka_native_metric = Arc::new(RdKafkaStats::new(registry.clone()));
         SourceMetrics {
             partition_input_count,
@@ -134,6 +176,10 @@ impl SourceMetrics {
             rdkafka_native_metric,
             connector_source_rows_received,
             direct_cdc_event_lag_latency,
+            cdc_snapshot_read_rows,
+            cdc_snapshot_read_position,
+            cdc_snapshot_restart_count,
+            cdc_snapshot_skipped_rows,
         }
     }
 }
@@ -28,3 +28,11 @@ pub async fn show_config(host: &str) -> anyhow::Result<()> {
     println!("{}", serde_json::to_string_pretty(&stream_config)?);
     Ok(())
 }
+
+pub async fn drain_hummock_events(host: &str) -> anyhow::Result<()> {
+    let listen_addr = HostAddr::try_from(host)?;
+    let client = ComputeClient::new(listen_addr).await?;
+    client.drain_hummock_events().await?;
+    println!("Done");
+    Ok(())
+}
@@ -433,3 +433,26 @@ pub async fn update_schedulability(
 
     Ok(())
 }
+
+/// Cordon the given workers, then generate and execute a reschedule plan that moves all of
+/// their actors onto the remaining schedulable workers. The workers are left cordoned
+/// afterwards so that no new actors will be placed on them.
+pub async fn drain(context: &CtlContext, workers: Vec<String>, yes: bool) -> anyhow::Result<()> {
+    update_schedulability(context, workers.clone(), Schedulability::Unschedulable).await?;
+
+    resize(
+        context,
+        ScaleCommandContext {
+            exclude_workers: Some(workers),
+            include_workers: None,
+            target_parallelism: None,
+            generate: false,
+            output: None,
+            yes,
+            fragments: None,
+            target_parallelism_per_worker: None,
+            exclusive_for_vertical: false,
+        },
+    )
+    .await
+}
@@ -145,6 +145,8 @@ pub enum DebugCommands {
 enum ComputeCommands {
     /// Show all the configuration parameters on compute node
     ShowConfig { host: String },
+    /// Wait until the hummock events enqueued on the compute node so far are processed
+    DrainHummockEvents { host: String },
 }
 
 #[derive(Subcommand)]
@@ -424,6 +426,21 @@ enum ScaleCommands {
         )]
         workers: Vec<String>,
     },
+    /// Cordon a compute node and reschedule all of its actors onto the remaining nodes
+    Drain {
+        /// Workers that need to be drained, both id and host are supported.
+        #[clap(
+            long,
+            required = true,
+            value_delimiter = ',',
+            value_name = "id or host,..."
+        )]
+        workers: Vec<String>,
+
+        /// Automatic yes to prompts
+        #[clap(short = 'y', long, default_value_t = false)]
+        yes: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -577,6 +594,9 @@ async fn start_impl(opts: CliOpts, context: &CtlContext) -> Result<()> {
         Commands::Compute(ComputeCommands::ShowConfig { host }) => {
             cmd_impl::compute::show_config(&host).await?
         }
+        Commands::Compute(ComputeCommands::DrainHummockEvents { host }) => {
+            cmd_impl::compute::drain_hummock_events(&host).await?
+        }
         Commands::Hummock(HummockCommands::DisableCommitEpoch) => {
             cmd_impl::hummock::disable_commit_epoch(context).await?
         }
@@ -803,6 +823,9 @@ async fn start_impl(opts: CliOpts, context: &CtlContext) -> Result<()> {
             cmd_impl::scale::update_schedulability(context, workers, Schedulability::Schedulable)
                 .await?
         }
+        Commands::Scale(ScaleCommands::Drain { workers, yes }) => {
+            cmd_impl::scale::drain(context, workers, yes).await?
+        }
         Commands::Debug(DebugCommands::Dump { common }) => cmd_impl::debug::dump(common).await?,
         Commands::Throttle(ThrottleCommands::Source(args)) => {
             apply_throttle(context, risingwave_pb::meta::PbThrottleTarget::Source, args).await?
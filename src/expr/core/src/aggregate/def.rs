@@ -50,6 +50,9 @@ pub struct AggCall {
     /// Should deduplicate the input before aggregation.
     pub distinct: bool,
 
+    /// Should ignore `NULL` input values. Only effective for `first_value`/`last_value`.
+    pub ignore_nulls: bool,
+
     /// Constant arguments.
     pub direct_args: Vec<LiteralExpression>,
 }
@@ -89,6 +92,7 @@ impl AggCall {
             column_orders,
             filter,
             distinct: agg_call.distinct,
+            ignore_nulls: agg_call.ignore_nulls,
             direct_args,
         })
     }
@@ -98,7 +102,7 @@ impl AggCall {
     /// # Syntax
     ///
     /// ```text
-    /// (<name>:<type> [<index>:<type>]* [distinct] [orderby [<index>:<asc|desc>]*])
+    /// (<name>:<type> [<index>:<type>]* [distinct] [ignore_nulls] [orderby [<index>:<asc|desc>]*])
     /// ```
     pub fn from_pretty(s: impl AsRef<str>) -> Self {
         let tokens = crate::expr::lexer(s.as_ref());
@@ -129,6 +133,7 @@ impl<Iter: Iterator<Item = Token>> Parser<Iter> {
         let ty = self.parse_type();
 
         let mut distinct = false;
+        let mut ignore_nulls = false;
         let mut children = Vec::new();
         let mut column_orders = Vec::new();
         while matches!(self.tokens.peek(), Some(Token::Index(_))) {
@@ -138,6 +143,10 @@ impl<Iter: Iterator<Item = Token>> Parser<Iter> {
             distinct = true;
             self.tokens.next(); // Consume
         }
+        if matches!(self.tokens.peek(), Some(Token::Literal(s)) if s == "ignore_nulls") {
+            ignore_nulls = true;
+            self.tokens.next(); // Consume
+        }
         if matches!(self.tokens.peek(), Some(Token::Literal(s)) if s == "orderby") {
             self.tokens.next(); // Consume
             while matches!(self.tokens.peek(), Some(Token::Index(_))) {
@@ -158,6 +167,7 @@ impl<Iter: Iterator<Item = Token>> Parser<Iter> {
             column_orders,
             filter: None,
             distinct,
+            ignore_nulls,
             direct_args: Vec::new(),
         }
     }
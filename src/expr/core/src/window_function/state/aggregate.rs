@@ -64,6 +64,7 @@ pub(super) fn new(call: &WindowFuncCall) -> Result<BoxedWindowState> {
         filter: None,
         // TODO(rc): support distinct on window function call? PG doesn't support it either.
         distinct: false,
+        ignore_nulls: false,
         direct_args: vec![],
     };
     let agg_func_sig = FUNCTION_REGISTRY
@@ -416,6 +416,7 @@ fn bench_expr(c: &mut Criterion) {
             column_orders: vec![],
             filter: None,
             distinct: false,
+            ignore_nulls: false,
             direct_args: vec![],
         }) {
             Ok(agg) => agg,
@@ -14,6 +14,11 @@
 
 use risingwave_expr::aggregate;
 
+/// Concatenates non-NULL values, each prefixed by the delimiter paired with it.
+/// Following Postgres, the delimiter paired with the first emitted value is unused,
+/// and a NULL delimiter concatenates directly, as if it were an empty string. This
+/// same function also replays the rows of the materialized input state in streaming
+/// `string_agg`, so the batch and streaming paths agree on NULL-delimiter handling.
 #[aggregate("string_agg(varchar, varchar) -> varchar")]
 fn string_agg(
     state: Option<Box<str>>,
@@ -57,6 +62,29 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_string_agg_null_delimiter() -> Result<()> {
+        // A NULL delimiter (`.` in the delimiter column) concatenates directly; the
+        // delimiter of the first emitted value is unused either way.
+        let chunk = StreamChunk::from_pretty(
+            " T   T
+            + aaa .
+            + bbb ,
+            + ccc .
+            + ddd _",
+        );
+        let string_agg = build_append_only(&AggCall::from_pretty(
+            "(string_agg:varchar $0:varchar $1:varchar)",
+        ))?;
+        let mut state = string_agg.create_state();
+        string_agg.update(&mut state, &chunk).await?;
+        assert_eq!(
+            string_agg.get_result(&state).await?,
+            Some("aaa,bbbccc_ddd".into())
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_string_agg_complex() -> Result<()> {
         let chunk = StreamChunk::from_pretty(
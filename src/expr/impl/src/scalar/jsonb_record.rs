@@ -0,0 +1,269 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use risingwave_common::array::{DataChunk, I32ArrayBuilder, StructRef, StructValue};
+use risingwave_common::types::{DataType, Datum, JsonbRef, ScalarImpl, ScalarRefImpl};
+use risingwave_expr::codegen::{async_trait, try_stream, BoxStream};
+use risingwave_expr::expr::{BoxedExpression, Context};
+use risingwave_expr::table_function::{BoxedTableFunction, TableFunction};
+use risingwave_expr::{build_function, function, ExprError, Result};
+
+/// Expands the top-level JSON object to a row having the composite type of the `base`
+/// argument. JSON fields matching struct field names are converted; missing fields take
+/// their value from `base`.
+#[function("jsonb_populate_record(struct, jsonb) -> struct")]
+fn jsonb_populate_record(
+    base: Option<StructRef<'_>>,
+    jsonb: JsonbRef<'_>,
+    ctx: &Context,
+) -> Result<StructValue> {
+    let output_type = ctx.return_type.as_struct();
+    jsonb.populate_struct(output_type, base).map_err(parse_err)
+}
+
+/// Like `jsonb_populate_record`, but falls back to case-insensitive key matching when
+/// no JSON key matches a field name exactly. Two JSON keys differing only by case that
+/// both match a field raise an error rather than silently picking one.
+#[function("jsonb_populate_record_ci(struct, jsonb) -> struct")]
+fn jsonb_populate_record_ci(
+    base: Option<StructRef<'_>>,
+    jsonb: JsonbRef<'_>,
+    ctx: &Context,
+) -> Result<StructValue> {
+    let output_type = ctx.return_type.as_struct();
+    jsonb
+        .populate_struct_ci(output_type, base)
+        .map_err(parse_err)
+}
+
+/// Expands the top-level JSON object to a row whose composite type is defined by an
+/// `AS` clause, as in `jsonb_to_record(...) AS x(a int, b text)`. The return type
+/// cannot be inferred from the argument, hence the custom `type_infer` error.
+#[function(
+    "jsonb_to_record(jsonb) -> struct",
+    type_infer = "record_type_requires_as_clause"
+)]
+fn jsonb_to_record(jsonb: JsonbRef<'_>, ctx: &Context) -> Result<StructValue> {
+    let output_type = ctx.return_type.as_struct();
+    jsonb.to_struct(output_type).map_err(parse_err)
+}
+
+/// Expands the top-level JSON array of objects to a set of rows having the composite
+/// type of the `base` argument.
+#[build_function("jsonb_populate_recordset(struct, jsonb) -> setof struct")]
+fn build_jsonb_populate_recordset(
+    return_type: DataType,
+    chunk_size: usize,
+    children: Vec<BoxedExpression>,
+) -> Result<BoxedTableFunction> {
+    let mut iter = children.into_iter();
+    let base = iter.next().unwrap();
+    let jsonb = iter.next().unwrap();
+    Ok(JsonbRecordset {
+        return_type,
+        chunk_size,
+        base: Some(base),
+        jsonb,
+        lax: false,
+    }
+    .boxed())
+}
+
+/// Like `jsonb_populate_recordset`, but silently skips array elements that are not JSON
+/// objects instead of erroring on them.
+#[build_function("jsonb_populate_recordset_lax(struct, jsonb) -> setof struct")]
+fn build_jsonb_populate_recordset_lax(
+    return_type: DataType,
+    chunk_size: usize,
+    children: Vec<BoxedExpression>,
+) -> Result<BoxedTableFunction> {
+    let mut iter = children.into_iter();
+    let base = iter.next().unwrap();
+    let jsonb = iter.next().unwrap();
+    Ok(JsonbRecordset {
+        return_type,
+        chunk_size,
+        base: Some(base),
+        jsonb,
+        lax: true,
+    }
+    .boxed())
+}
+
+/// Expands the top-level JSON array of objects to a set of rows whose composite type is
+/// defined by an `AS` clause.
+#[build_function(
+    "jsonb_to_recordset(jsonb) -> setof struct",
+    type_infer = "record_type_requires_as_clause"
+)]
+fn build_jsonb_to_recordset(
+    return_type: DataType,
+    chunk_size: usize,
+    children: Vec<BoxedExpression>,
+) -> Result<BoxedTableFunction> {
+    let mut iter = children.into_iter();
+    let jsonb = iter.next().unwrap();
+    Ok(JsonbRecordset {
+        return_type,
+        chunk_size,
+        base: None,
+        jsonb,
+        lax: false,
+    }
+    .boxed())
+}
+
+/// Like `jsonb_to_recordset`, but silently skips array elements that are not JSON
+/// objects instead of erroring on them.
+#[build_function(
+    "jsonb_to_recordset_lax(jsonb) -> setof struct",
+    type_infer = "record_type_requires_as_clause"
+)]
+fn build_jsonb_to_recordset_lax(
+    return_type: DataType,
+    chunk_size: usize,
+    children: Vec<BoxedExpression>,
+) -> Result<BoxedTableFunction> {
+    let mut iter = children.into_iter();
+    let jsonb = iter.next().unwrap();
+    Ok(JsonbRecordset {
+        return_type,
+        chunk_size,
+        base: None,
+        jsonb,
+        lax: true,
+    }
+    .boxed())
+}
+
+/// The table function behind `jsonb_populate_recordset` and `jsonb_to_recordset`, as
+/// well as their `_lax` variants.
+#[derive(Debug)]
+struct JsonbRecordset {
+    return_type: DataType,
+    chunk_size: usize,
+    /// The `base` row expression. `None` for `jsonb_to_recordset`.
+    base: Option<BoxedExpression>,
+    jsonb: BoxedExpression,
+    /// Whether to skip non-object array elements instead of erroring on them.
+    lax: bool,
+}
+
+#[async_trait]
+impl TableFunction for JsonbRecordset {
+    fn return_type(&self) -> DataType {
+        self.return_type.clone()
+    }
+
+    async fn eval<'a>(&'a self, input: &'a DataChunk) -> BoxStream<'a, Result<DataChunk>> {
+        self.eval_inner(input)
+    }
+}
+
+impl JsonbRecordset {
+    /// The SQL name of the function, for error messages.
+    fn name(&self) -> &'static str {
+        match (&self.base, self.lax) {
+            (Some(_), false) => "jsonb_populate_recordset",
+            (Some(_), true) => "jsonb_populate_recordset_lax",
+            (None, false) => "jsonb_to_recordset",
+            (None, true) => "jsonb_to_recordset_lax",
+        }
+    }
+
+    #[try_stream(boxed, ok = DataChunk, error = ExprError)]
+    async fn eval_inner<'a>(&'a self, input: &'a DataChunk) {
+        let base_array = match &self.base {
+            Some(expr) => Some(expr.eval(input).await?),
+            None => None,
+        };
+        let jsonb_array = self.jsonb.eval(input).await?;
+
+        let output_type = self.return_type.as_struct();
+        let mut index_builder = I32ArrayBuilder::new(self.chunk_size);
+        let mut value_builder = self.return_type.create_array_builder(self.chunk_size);
+        for i in 0..input.capacity() {
+            if !input.visibility().is_set(i) {
+                continue;
+            }
+            // A NULL or JSON `null` input produces no rows.
+            let Some(ScalarRefImpl::Jsonb(jsonb)) = jsonb_array.value_at(i) else {
+                continue;
+            };
+            if jsonb.is_jsonb_null() {
+                continue;
+            }
+            if !jsonb.is_array() {
+                return Err(parse_err(format!(
+                    "cannot call {} on a non-array",
+                    self.name()
+                )));
+            }
+            let base = match &base_array {
+                Some(array) => match array.value_at(i) {
+                    Some(ScalarRefImpl::Struct(base)) => Some(base),
+                    _ => None,
+                },
+                None => None,
+            };
+            for elem in jsonb.array_elements().map_err(parse_err)? {
+                if self.lax && !elem.is_object() {
+                    continue;
+                }
+                let value = elem.populate_struct(output_type, base).map_err(parse_err)?;
+                index_builder.append(Some(i as i32));
+                let datum: Datum = Some(ScalarImpl::Struct(value));
+                value_builder.append(datum);
+
+                if index_builder.len() == self.chunk_size {
+                    let index_array = std::mem::replace(
+                        &mut index_builder,
+                        I32ArrayBuilder::new(self.chunk_size),
+                    )
+                    .finish()
+                    .into_ref();
+                    let value_array = std::mem::replace(
+                        &mut value_builder,
+                        self.return_type.create_array_builder(self.chunk_size),
+                    )
+                    .finish()
+                    .into_ref();
+                    yield DataChunk::new(vec![index_array, value_array], self.chunk_size);
+                }
+            }
+        }
+
+        if index_builder.len() > 0 {
+            let len = index_builder.len();
+            let index_array = index_builder.finish().into_ref();
+            let value_array = value_builder.finish().into_ref();
+            yield DataChunk::new(vec![index_array, value_array], len);
+        }
+    }
+}
+
+/// The return type of `jsonb_to_record`-style functions is given by the user rather
+/// than inferred from the arguments. Return a readable error instead of panicking when
+/// inference is attempted, e.g. when the `AS` clause is missing.
+fn record_type_requires_as_clause(_args: &[DataType]) -> Result<DataType> {
+    Err(ExprError::UnsupportedFunction(
+        "a column definition list is required for functions returning \"record\", \
+         e.g. `... AS t(a int, b text)`"
+            .into(),
+    ))
+}
+
+fn parse_err(e: String) -> ExprError {
+    ExprError::Parse(e.into())
+}
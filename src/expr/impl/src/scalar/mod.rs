@@ -57,6 +57,7 @@ mod jsonb_delete;
 mod jsonb_info;
 mod jsonb_object;
 mod jsonb_path;
+mod jsonb_record;
 mod length;
 mod lower;
 mod make_time;
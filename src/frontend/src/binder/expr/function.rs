@@ -155,7 +155,14 @@ impl Binder {
         }
 
         // table function
-        if let Ok(function_type) = TableFunctionType::from_str(function_name.as_str()) {
+        let table_function_name = match function_name.as_str() {
+            // RisingWave stores `json` data as `jsonb`, so the PostgreSQL `json_*`
+            // record functions are aliases of their `jsonb_*` counterparts.
+            "json_populate_recordset" => "jsonb_populate_recordset",
+            "json_to_recordset" => "jsonb_to_recordset",
+            name => name,
+        };
+        if let Ok(function_type) = TableFunctionType::from_str(table_function_name) {
             self.ensure_table_function_allowed()?;
             return Ok(TableFunction::new(function_type, inputs)?.into());
         }
@@ -1125,6 +1132,13 @@ impl Binder {
                 ("jsonb_path_exists", raw_call(ExprType::JsonbPathExists)),
                 ("jsonb_path_query_array", raw_call(ExprType::JsonbPathQueryArray)),
                 ("jsonb_path_query_first", raw_call(ExprType::JsonbPathQueryFirst)),
+                ("jsonb_populate_record", raw_call(ExprType::JsonbPopulateRecord)),
+                ("jsonb_populate_record_ci", raw_call(ExprType::JsonbPopulateRecordCi)),
+                ("jsonb_to_record", raw_call(ExprType::JsonbToRecord)),
+                // RisingWave stores `json` data as `jsonb`, so the `json_*` record
+                // functions are aliases of their `jsonb_*` counterparts.
+                ("json_populate_record", raw_call(ExprType::JsonbPopulateRecord)),
+                ("json_to_record", raw_call(ExprType::JsonbToRecord)),
                 // Functions that return a constant value
                 ("pi", pi()),
                 // greatest and least
@@ -1330,6 +1344,8 @@ impl Binder {
                 ("pg_is_in_recovery", raw_literal(ExprImpl::literal_bool(false))),
                 // internal
                 ("rw_vnode", raw_call(ExprType::Vnode)),
+                ("rw_cordon_worker", raw_call(ExprType::RwCordonWorker)),
+                ("rw_uncordon_worker", raw_call(ExprType::RwUncordonWorker)),
                 // TODO: choose which pg version we should return.
                 ("version", raw_literal(ExprImpl::literal_varchar(current_cluster_version()))),
                 // non-deterministic
@@ -44,6 +44,10 @@ pub struct CorrelatedInputRef {
 
 impl CorrelatedInputRef {
     pub fn new(index: usize, data_type: DataType, depth: usize) -> Self {
+        debug_assert_ne!(
+            depth, 0,
+            "the relative depth of a correlated input ref should be non-zero"
+        );
         CorrelatedInputRef {
             index,
             data_type,
@@ -56,6 +60,12 @@ impl CorrelatedInputRef {
         self.index
     }
 
+    /// Returns a copy of this reference with its relative depth replaced by `new_depth`,
+    /// e.g. when a subquery level is collapsed during unnesting.
+    pub fn with_depth(&self, new_depth: Depth) -> Self {
+        Self::new(self.index, self.data_type.clone(), new_depth)
+    }
+
     pub fn depth(&self) -> usize {
         match self.position {
             Position::Relative(depth) => depth,
@@ -93,3 +103,16 @@ impl fmt::Debug for CorrelatedInputRef {
             .finish()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic]
+    fn test_zero_depth_rejected() {
+        // A zero depth means the reference is not correlated at all and would silently
+        // break decorrelation, so constructing one should be caught early.
+        CorrelatedInputRef::new(0, DataType::Int32, 0);
+    }
+}
@@ -20,3 +20,4 @@ mod pg_get_userbyid;
 mod pg_get_viewdef;
 mod pg_indexes_size;
 mod pg_relation_size;
+mod rw_cordon_worker;
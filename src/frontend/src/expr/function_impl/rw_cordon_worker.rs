@@ -0,0 +1,53 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_expr::{capture_context, function, ExprError, Result};
+use risingwave_pb::meta::update_worker_node_schedulability_request::Schedulability;
+
+use super::context::META_CLIENT;
+use crate::meta_client::FrontendMetaClient;
+
+/// Marks a worker node as unschedulable so that no new streaming actors will be scheduled
+/// on it, without affecting the actors already running there. The cordoned state is
+/// visible in `rw_catalog.rw_worker_nodes` as `is_unschedulable` once this returns.
+#[function("rw_cordon_worker(int4) -> boolean", volatile)]
+async fn rw_cordon_worker(worker_id: i32) -> Result<bool> {
+    update_schedulability(worker_id, Schedulability::Unschedulable).await
+}
+
+/// Reverts [`rw_cordon_worker`], making the worker schedulable again.
+#[function("rw_uncordon_worker(int4) -> boolean", volatile)]
+async fn rw_uncordon_worker(worker_id: i32) -> Result<bool> {
+    update_schedulability(worker_id, Schedulability::Schedulable).await
+}
+
+async fn update_schedulability(worker_id: i32, schedulability: Schedulability) -> Result<bool> {
+    // The captured function is sync, so capture the meta client first and issue the rpc
+    // outside of the task-local scope access.
+    let meta_client = get_meta_client_captured()?;
+    meta_client
+        .update_worker_node_schedulability(vec![worker_id as u32], schedulability)
+        .await
+        .map_err(|e| ExprError::Internal(e.into()))?;
+    Ok(true)
+}
+
+#[capture_context(META_CLIENT)]
+fn get_meta_client(
+    meta_client: &Arc<dyn FrontendMetaClient>,
+) -> Result<Arc<dyn FrontendMetaClient>> {
+    Ok(meta_client.clone())
+}
@@ -206,6 +206,9 @@ impl ExprVisitor for ImpureAnalyzer {
             | expr_node::Type::JsonbPathMatch
             | expr_node::Type::JsonbPathQueryArray
             | expr_node::Type::JsonbPathQueryFirst
+            | expr_node::Type::JsonbPopulateRecord
+            | expr_node::Type::JsonbPopulateRecordCi
+            | expr_node::Type::JsonbToRecord
             | expr_node::Type::IsJson
             | expr_node::Type::ToJsonb
             | expr_node::Type::Sind
@@ -255,6 +258,8 @@ impl ExprVisitor for ImpureAnalyzer {
             | expr_node::Type::PgSleepFor
             | expr_node::Type::PgSleepUntil
             | expr_node::Type::CastRegclass
+            | expr_node::Type::RwCordonWorker
+            | expr_node::Type::RwUncordonWorker
             | expr_node::Type::PgGetIndexdef
             | expr_node::Type::ColDescription
             | expr_node::Type::PgGetViewdef
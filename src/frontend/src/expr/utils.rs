@@ -16,7 +16,7 @@ use fixedbitset::FixedBitSet;
 use risingwave_common::types::{DataType, ScalarImpl};
 use risingwave_pb::expr::expr_node::Type;
 
-use super::{Expr, ExprImpl, ExprRewriter, ExprVisitor, FunctionCall, InputRef};
+use super::{CorrelatedInputRef, Expr, ExprImpl, ExprRewriter, ExprVisitor, FunctionCall, InputRef};
 use crate::expr::ExprType;
 
 fn split_expr_by(expr: ExprImpl, op: ExprType, rets: &mut Vec<ExprImpl>) {
@@ -402,6 +402,28 @@ pub fn collect_input_refs<'a>(
     input_ref_collector.into()
 }
 
+/// Shift the relative depth of every `CorrelatedInputRef` in the expression by `delta`,
+/// e.g. decrement it by one when a subquery level is collapsed during unnesting.
+pub struct ShiftCorrelatedInputRefDepth {
+    delta: isize,
+}
+
+impl ShiftCorrelatedInputRefDepth {
+    pub fn new(delta: isize) -> Self {
+        Self { delta }
+    }
+}
+
+impl ExprRewriter for ShiftCorrelatedInputRefDepth {
+    fn rewrite_correlated_input_ref(&mut self, input_ref: CorrelatedInputRef) -> ExprImpl {
+        let new_depth = input_ref
+            .depth()
+            .checked_add_signed(self.delta)
+            .expect("the shifted depth of a correlated input ref should not underflow");
+        input_ref.with_depth(new_depth).into()
+    }
+}
+
 /// Count `Now`s in the expression.
 #[derive(Clone, Default)]
 pub struct CountNow {
@@ -610,8 +632,28 @@ mod tests {
     use risingwave_common::types::{DataType, ScalarImpl};
     use risingwave_pb::expr::expr_node::Type;
 
-    use super::{fold_boolean_constant, push_down_not};
-    use crate::expr::{ExprImpl, FunctionCall, InputRef};
+    use super::{fold_boolean_constant, push_down_not, ShiftCorrelatedInputRefDepth};
+    use crate::expr::{CorrelatedInputRef, ExprImpl, ExprRewriter, FunctionCall, InputRef};
+
+    #[test]
+    fn test_shift_correlated_input_ref_depth() {
+        // expr := CorrelatedInputRef(depth = 2) + 1
+        let expr: ExprImpl = FunctionCall::new(
+            Type::Add,
+            vec![
+                CorrelatedInputRef::new(0, DataType::Int32, 2).into(),
+                ExprImpl::literal_int(1),
+            ],
+        )
+        .unwrap()
+        .into();
+
+        let res = ShiftCorrelatedInputRefDepth::new(-1).rewrite_expr(expr);
+
+        let res = res.as_function_call().unwrap();
+        let correlated = res.inputs()[0].as_correlated_input_ref().unwrap();
+        assert_eq!(correlated.depth(), 1);
+    }
 
     #[test]
     fn constant_boolean_folding_basic_and() {
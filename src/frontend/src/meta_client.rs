@@ -31,6 +31,7 @@ use risingwave_pb::meta::list_fragment_distribution_response::FragmentDistributi
 use risingwave_pb::meta::list_object_dependencies_response::PbObjectDependencies;
 use risingwave_pb::meta::list_table_fragment_states_response::TableFragmentState;
 use risingwave_pb::meta::list_table_fragments_response::TableFragmentInfo;
+use risingwave_pb::meta::update_worker_node_schedulability_request::Schedulability;
 use risingwave_pb::meta::EventLog;
 use risingwave_rpc_client::error::Result;
 use risingwave_rpc_client::{HummockMetaClient, MetaClient};
@@ -109,6 +110,12 @@ pub trait FrontendMetaClient: Send + Sync {
     async fn list_all_nodes(&self) -> Result<Vec<WorkerNode>>;
 
     async fn list_compact_task_progress(&self) -> Result<Vec<CompactTaskProgress>>;
+
+    async fn update_worker_node_schedulability(
+        &self,
+        worker_ids: Vec<u32>,
+        schedulability: Schedulability,
+    ) -> Result<()>;
 }
 
 pub struct FrontendMetaClientImpl(pub MetaClient);
@@ -270,4 +277,15 @@ impl FrontendMetaClient for FrontendMetaClientImpl {
     async fn list_compact_task_progress(&self) -> Result<Vec<CompactTaskProgress>> {
         self.0.list_compact_task_progress().await
     }
+
+    async fn update_worker_node_schedulability(
+        &self,
+        worker_ids: Vec<u32>,
+        schedulability: Schedulability,
+    ) -> Result<()> {
+        self.0
+            .update_schedulability(&worker_ids, schedulability)
+            .await
+            .map(|_| ())
+    }
 }
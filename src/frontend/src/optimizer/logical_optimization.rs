@@ -27,7 +27,8 @@ use crate::optimizer::plan_rewriter::ShareSourceRewriter;
 #[cfg(debug_assertions)]
 use crate::optimizer::plan_visitor::InputRefValidator;
 use crate::optimizer::plan_visitor::{
-    has_logical_apply, HasMaxOneRowApply, PlanCheckApplyEliminationExt, PlanVisitor,
+    has_logical_apply, HasMaxOneRowApply, PlanCheckApplyEliminationExt,
+    PlanCheckCorrelatedRefEliminationExt, PlanVisitor,
 };
 use crate::optimizer::rule::*;
 use crate::optimizer::PlanRef;
@@ -370,6 +371,23 @@ static ALWAYS_FALSE_FILTER: LazyLock<OptimizationStage> = LazyLock::new(|| {
     )
 });
 
+static STREAM_FILTER_EXPRESSION_SIMPLIFY: LazyLock<OptimizationStage> = LazyLock::new(|| {
+    OptimizationStage::new(
+        "Simplify Stream Filter Expression",
+        vec![StreamFilterExpressionSimplifyRule::create()],
+        ApplyOrder::TopDown,
+    )
+});
+
+static STREAM_FILTER_EXPRESSION_SIMPLIFY_AGGRESSIVE: LazyLock<OptimizationStage> =
+    LazyLock::new(|| {
+        OptimizationStage::new(
+            "Simplify Stream Filter Expression (Aggressive)",
+            vec![StreamFilterExpressionSimplifyRule::create_aggressive()],
+            ApplyOrder::TopDown,
+        )
+    });
+
 static LIMIT_PUSH_DOWN: LazyLock<OptimizationStage> = LazyLock::new(|| {
     OptimizationStage::new(
         "Push Down Limit",
@@ -463,6 +481,9 @@ impl LogicalOptimizer {
 
         // Check if all `Apply`s are eliminated and the subquery is unnested.
         plan.check_apply_elimination()?;
+        // A stray correlated input ref would panic at proto-conversion time, so report it
+        // as a planner error here instead.
+        plan.check_correlated_ref_elimination()?;
 
         Ok(plan)
     }
@@ -564,6 +585,20 @@ impl LogicalOptimizer {
             bail!("Scalar subquery might produce more than one row.");
         }
 
+        // Simplify tautology / contradiction patterns in filter predicates. Which mode
+        // is used is controlled by `streaming_filter_simplify_aggressive`. This must
+        // run before predicate push-down, which absorbs filters into the scans and
+        // leaves no `LogicalFilter` for the rule to match.
+        if ctx
+            .session_ctx()
+            .config()
+            .streaming_filter_simplify_aggressive()
+        {
+            plan = plan.optimize_by_rules(&STREAM_FILTER_EXPRESSION_SIMPLIFY_AGGRESSIVE);
+        } else {
+            plan = plan.optimize_by_rules(&STREAM_FILTER_EXPRESSION_SIMPLIFY);
+        }
+
         // Predicate Push-down
         plan = Self::predicate_pushdown(plan, explain_trace, &ctx);
 
@@ -284,12 +284,17 @@ impl Strong {
             | ExprType::JsonbPathMatch
             | ExprType::JsonbPathQueryArray
             | ExprType::JsonbPathQueryFirst
+            | ExprType::JsonbPopulateRecord
+            | ExprType::JsonbPopulateRecordCi
+            | ExprType::JsonbToRecord
             | ExprType::Vnode
             | ExprType::Proctime
             | ExprType::PgSleep
             | ExprType::PgSleepFor
             | ExprType::PgSleepUntil
             | ExprType::CastRegclass
+            | ExprType::RwCordonWorker
+            | ExprType::RwUncordonWorker
             | ExprType::PgGetIndexdef
             | ExprType::ColDescription
             | ExprType::PgGetViewdef
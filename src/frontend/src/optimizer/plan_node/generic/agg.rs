@@ -763,6 +763,8 @@ impl PlanAggCall {
             distinct: self.distinct,
             order_by: self.order_by.iter().map(ColumnOrder::to_protobuf).collect(),
             filter: self.filter.as_expr_unless_true().map(|x| x.to_expr_proto()),
+            // TODO: support `IGNORE NULLS` in the frontend
+            ignore_nulls: false,
             direct_args: self
                 .direct_args
                 .iter()
@@ -0,0 +1,135 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{DefaultBehavior, DefaultValue};
+use crate::error::{ErrorCode, RwError};
+use crate::expr::{CorrelatedInputRef, ExprVisitor};
+use crate::optimizer::plan_node::{
+    LogicalAgg, LogicalFilter, LogicalJoin, LogicalProject, LogicalProjectSet,
+    LogicalTableFunction, PlanTreeNode,
+};
+use crate::optimizer::plan_visitor::PlanVisitor;
+use crate::PlanRef;
+
+#[derive(Default)]
+struct StrayCorrelatedRefCollector {
+    stray_refs: Vec<String>,
+}
+
+impl ExprVisitor for StrayCorrelatedRefCollector {
+    fn visit_correlated_input_ref(&mut self, correlated_input_ref: &CorrelatedInputRef) {
+        self.stray_refs.push(format!(
+            "{{ index: {}, depth: {}, correlated_id: {} }}",
+            correlated_input_ref.index(),
+            correlated_input_ref.depth(),
+            correlated_input_ref.correlated_id(),
+        ));
+    }
+}
+
+/// Collects `CorrelatedInputRef`s that survived decorrelation. Any such reference would
+/// panic later in `to_expr_proto`, so they are reported as a planner error instead.
+#[derive(Default)]
+pub struct CorrelatedRefValidator {
+    collector: StrayCorrelatedRefCollector,
+}
+
+impl PlanVisitor for CorrelatedRefValidator {
+    /// `correlated_input_ref` can only appear in `LogicalProject`, `LogicalFilter`,
+    /// `LogicalJoin` or the `filter` clause of `PlanAggCall` of `LogicalAgg` now.
+
+    type Result = ();
+
+    type DefaultBehavior = impl DefaultBehavior<Self::Result>;
+
+    fn default_behavior() -> Self::DefaultBehavior {
+        DefaultValue
+    }
+
+    fn visit_logical_join(&mut self, plan: &LogicalJoin) {
+        plan.on().visit_expr(&mut self.collector);
+
+        plan.inputs()
+            .into_iter()
+            .for_each(|input| self.visit(input));
+    }
+
+    fn visit_logical_filter(&mut self, plan: &LogicalFilter) {
+        plan.predicate().visit_expr(&mut self.collector);
+
+        plan.inputs()
+            .into_iter()
+            .for_each(|input| self.visit(input));
+    }
+
+    fn visit_logical_project(&mut self, plan: &LogicalProject) {
+        plan.exprs()
+            .iter()
+            .for_each(|expr| self.collector.visit_expr(expr));
+
+        plan.inputs()
+            .into_iter()
+            .for_each(|input| self.visit(input));
+    }
+
+    fn visit_logical_agg(&mut self, plan: &LogicalAgg) {
+        plan.agg_calls()
+            .iter()
+            .for_each(|agg_call| agg_call.filter.visit_expr(&mut self.collector));
+
+        plan.inputs()
+            .into_iter()
+            .for_each(|input| self.visit(input));
+    }
+
+    fn visit_logical_project_set(&mut self, plan: &LogicalProjectSet) {
+        plan.select_list()
+            .iter()
+            .for_each(|expr| self.collector.visit_expr(expr));
+
+        plan.inputs()
+            .into_iter()
+            .for_each(|input| self.visit(input));
+    }
+
+    fn visit_logical_table_function(&mut self, plan: &LogicalTableFunction) {
+        plan.table_function
+            .args
+            .iter()
+            .for_each(|expr| self.collector.visit_expr(expr));
+
+        plan.inputs()
+            .into_iter()
+            .for_each(|input| self.visit(input));
+    }
+}
+
+#[easy_ext::ext(PlanCheckCorrelatedRefEliminationExt)]
+impl PlanRef {
+    /// Checks that no `CorrelatedInputRef` is left in the plan after decorrelation.
+    pub fn check_correlated_ref_elimination(&self) -> Result<(), RwError> {
+        let mut validator = CorrelatedRefValidator::default();
+        validator.visit(self.clone());
+        let stray_refs = validator.collector.stray_refs;
+        if stray_refs.is_empty() {
+            Ok(())
+        } else {
+            Err(ErrorCode::InternalError(format!(
+                "correlated input refs survived decorrelation: [{}]",
+                stray_refs.join(", ")
+            ))
+            .into())
+        }
+    }
+}
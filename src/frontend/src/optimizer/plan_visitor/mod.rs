@@ -17,6 +17,8 @@ mod apply_visitor;
 pub use apply_visitor::*;
 mod plan_correlated_id_finder;
 pub use plan_correlated_id_finder::*;
+mod correlated_ref_validator;
+pub use correlated_ref_validator::*;
 mod share_parent_counter;
 pub use share_parent_counter::*;
 
@@ -16,4 +16,5 @@ pub(crate) mod bushy_tree_join_ordering_rule;
 pub(crate) mod filter_with_now_to_join_rule;
 pub(crate) mod split_now_and_rule;
 pub(crate) mod split_now_or_rule;
+pub(crate) mod stream_filter_expression_simplify_rule;
 pub(crate) mod stream_project_merge_rule;
@@ -0,0 +1,568 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use fixedbitset::FixedBitSet;
+use risingwave_common::types::ScalarImpl;
+
+use crate::expr::{Expr, ExprImpl, ExprType, ExprVisitor, FunctionCall, InputRef};
+use crate::optimizer::plan_expr_visitor::Strong;
+use crate::optimizer::plan_node::{LogicalFilter, PlanTreeNodeUnary};
+use crate::optimizer::{BoxedRule, PlanRef, Rule};
+use crate::utils::Condition;
+
+/// Simplify tautology / contradiction patterns in the predicate of a streaming
+/// [`LogicalFilter`].
+///
+/// The main pattern is `(NOT e) OR e`, which keeps a row iff `e` evaluates to non-NULL.
+/// In the conservative mode (the default) it is rewritten to a conjunction of
+/// `IS NOT NULL` over the involved columns, preserving the three-valued-logic behavior
+/// of the original predicate; this requires proving that `e` is strict
+/// (null-rejecting) in each involved column, and non-strict predicates are left
+/// unchanged. In the aggressive mode (enabled by the session variable
+/// `streaming_filter_simplify_aggressive`) it is folded to plain `true`, which
+/// additionally keeps rows whose column is NULL.
+///
+/// The rule matches any [`LogicalFilter`] regardless of its input, so it also fires
+/// when the filter sits above a projection or a share node.
+pub struct StreamFilterExpressionSimplifyRule {
+    aggressive: bool,
+}
+
+/// Tracing target for the rule's decisions. Attach
+/// `RUST_LOG=rw_optimizer::filter_simplify=debug` to see the original predicate,
+/// which pattern (if any) fired, and the rewritten result, without reading a plan
+/// diff.
+const TRACE_TARGET: &str = "rw_optimizer::filter_simplify";
+
+impl Rule for StreamFilterExpressionSimplifyRule {
+    fn apply(&self, plan: PlanRef) -> Option<PlanRef> {
+        let filter = plan.as_logical_filter()?;
+        let mut changed = false;
+        let predicate = Condition {
+            conjunctions: filter
+                .predicate()
+                .conjunctions
+                .iter()
+                .map(|expr| match self.simplify_conjunction(expr) {
+                    Some(simplified) => {
+                        tracing::debug!(
+                            target: TRACE_TARGET,
+                            original = ?expr,
+                            simplified = ?simplified,
+                            "simplified filter conjunction"
+                        );
+                        changed = true;
+                        simplified
+                    }
+                    None => {
+                        tracing::debug!(
+                            target: TRACE_TARGET,
+                            original = ?expr,
+                            "filter conjunction left unchanged"
+                        );
+                        expr.clone()
+                    }
+                })
+                .collect(),
+        };
+        changed.then(|| LogicalFilter::create(filter.input(), predicate))
+    }
+}
+
+impl StreamFilterExpressionSimplifyRule {
+    /// Create the rule in the conservative (`IS NOT NULL`-preserving) mode.
+    pub fn create() -> BoxedRule {
+        Box::new(StreamFilterExpressionSimplifyRule { aggressive: false })
+    }
+
+    /// Create the rule in the aggressive mode, folding tautologies to plain `true`.
+    pub fn create_aggressive() -> BoxedRule {
+        Box::new(StreamFilterExpressionSimplifyRule { aggressive: true })
+    }
+
+    /// Try to simplify a single top-level conjunction of the filter predicate.
+    /// Returns `None` if no pattern matched.
+    fn simplify_conjunction(&self, expr: &ExprImpl) -> Option<ExprImpl> {
+        let func_call = expr.as_function_call()?;
+        match func_call.func_type() {
+            ExprType::Or => {
+                let [e1, e2] = func_call.inputs() else {
+                    return None;
+                };
+                if let Some(simplified) = check_special_pattern(e1, e2, ExprType::Or) {
+                    tracing::debug!(
+                        target: TRACE_TARGET,
+                        "`check_special_pattern` matched: `OR` with a constant true operand"
+                    );
+                    return Some(simplified);
+                }
+                let simplified = self.check_optimizable_pattern(e1, e2);
+                if simplified.is_some() {
+                    tracing::debug!(
+                        target: TRACE_TARGET,
+                        aggressive = self.aggressive,
+                        "`check_optimizable_pattern` matched: `(NOT e) OR e` tautology"
+                    );
+                }
+                simplified
+            }
+            ExprType::And => {
+                let [e1, e2] = func_call.inputs() else {
+                    return None;
+                };
+                let simplified = check_special_pattern(e1, e2, ExprType::And);
+                if simplified.is_some() {
+                    tracing::debug!(
+                        target: TRACE_TARGET,
+                        "`check_special_pattern` matched: `AND` with a constant false operand"
+                    );
+                }
+                simplified
+            }
+            // `e IS NOT DISTINCT FROM e` is always true and `e IS DISTINCT FROM e` is
+            // always false for a pure `e`, even when `e` is NULL: unlike `=`, these
+            // comparisons are null-safe, so no `IS NOT NULL` wrapping is needed.
+            func_type @ (ExprType::IsDistinctFrom | ExprType::IsNotDistinctFrom) => {
+                let [e1, e2] = func_call.inputs() else {
+                    return None;
+                };
+                (e1 == e2 && e1.is_pure()).then(|| {
+                    tracing::debug!(
+                        target: TRACE_TARGET,
+                        "self-comparison with `IS [NOT] DISTINCT FROM` folded to a constant"
+                    );
+                    ExprImpl::literal_bool(func_type == ExprType::IsNotDistinctFrom)
+                })
+            }
+            ExprType::Case => {
+                let simplified = simplify_const_case(func_call);
+                if simplified.is_some() {
+                    tracing::debug!(
+                        target: TRACE_TARGET,
+                        "const-foldable `CASE` collapsed to the selected branch"
+                    );
+                }
+                simplified
+            }
+            _ => None,
+        }
+    }
+
+    /// Check the tautology pattern `(NOT e) OR e` (in either operand order) and return
+    /// the simplified expression if matched.
+    fn check_optimizable_pattern(&self, e1: &ExprImpl, e2: &ExprImpl) -> Option<ExprImpl> {
+        fn strip_not(expr: &ExprImpl) -> Option<&ExprImpl> {
+            let func_call = expr.as_function_call()?;
+            (func_call.func_type() == ExprType::Not).then(|| &func_call.inputs()[0])
+        }
+
+        let e = if strip_not(e1) == Some(e2) {
+            e2
+        } else if strip_not(e2) == Some(e1) {
+            e1
+        } else {
+            return None;
+        };
+
+        // Structural equality of two impure subexpressions does not make a per-row
+        // tautology, since each occurrence is evaluated separately.
+        if !e.is_pure() {
+            return None;
+        }
+
+        if self.aggressive {
+            return Some(ExprImpl::literal_bool(true));
+        }
+
+        // Conservative: the original predicate keeps a row iff `e` is non-NULL, which
+        // matches `IS NOT NULL` over the involved columns only when `e` is strict
+        // (null-rejecting) in each of them. That is not the case for e.g.
+        // `v1 IS NULL`, which never yields NULL and makes the original predicate
+        // constantly true, so the rewrite would wrongly drop NULL rows there. Use the
+        // [`Strong`] analyzer to prove strictness and leave the predicate alone
+        // otherwise.
+        let mut collector = InputRefCollector::default();
+        collector.visit_expr(e);
+        let input_refs = collector.input_refs;
+        let capacity = input_refs.iter().map(|r| r.index() + 1).max()?;
+        // `e` must not be NULL when no column is NULL, e.g. `v1 = NULL`; otherwise
+        // the original predicate drops every row while the rewrite would not.
+        if Strong::is_null(e, FixedBitSet::with_capacity(capacity)) {
+            return None;
+        }
+        for input_ref in &input_refs {
+            let mut null_columns = FixedBitSet::with_capacity(capacity);
+            null_columns.insert(input_ref.index());
+            if !Strong::is_null(e, null_columns) {
+                return None;
+            }
+        }
+
+        // Wrap each involved column with `IS NOT NULL` and AND them together.
+        let mut input_refs = input_refs.into_iter();
+        let mut result: ExprImpl =
+            FunctionCall::new(ExprType::IsNotNull, vec![input_refs.next()?.into()])
+                .ok()?
+                .into();
+        for input_ref in input_refs {
+            let is_not_null: ExprImpl =
+                FunctionCall::new(ExprType::IsNotNull, vec![input_ref.into()])
+                    .ok()?
+                    .into();
+            result = FunctionCall::new(ExprType::And, vec![result, is_not_null])
+                .ok()?
+                .into();
+        }
+        Some(result)
+    }
+}
+
+/// Check the pattern `e OR true` / `e AND false` (and mirrored) and fold it to the
+/// constant, since the other operand cannot change the outcome.
+fn check_special_pattern(e1: &ExprImpl, e2: &ExprImpl, op: ExprType) -> Option<ExprImpl> {
+    let fold_to = match op {
+        ExprType::Or => true,
+        ExprType::And => false,
+        _ => return None,
+    };
+    // Both operands get the same treatment: a const-foldable expression like `1 = 1`
+    // counts just as much as a plain literal, regardless of which side it is on.
+    for e in [e1, e2] {
+        if let Some(Ok(Some(scalar))) = e.try_fold_const()
+            && scalar == ScalarImpl::Bool(fold_to)
+        {
+            return Some(ExprImpl::literal_bool(fold_to));
+        }
+    }
+    None
+}
+
+/// Collapse a `CASE` expression whose `WHEN` conditions are const-foldable to the
+/// selected branch, so the streaming filter doesn't evaluate the branches row by row.
+/// The inputs of a `Case` call are `[when_1, then_1, ..., when_n, then_n]` plus an
+/// optional trailing `ELSE` arm.
+fn simplify_const_case(func_call: &FunctionCall) -> Option<ExprImpl> {
+    let mut chunks = func_call.inputs().chunks_exact(2);
+    for chunk in chunks.by_ref() {
+        let [when, then] = chunk else {
+            unreachable!();
+        };
+        match when.try_fold_const()? {
+            Ok(Some(ScalarImpl::Bool(true))) => return Some(then.clone()),
+            // A NULL condition behaves like `false`: fall through to the next arm.
+            Ok(Some(ScalarImpl::Bool(false))) | Ok(None) => {}
+            // Not a boolean or fails to evaluate; leave the expression alone.
+            _ => return None,
+        }
+    }
+    match chunks.remainder() {
+        [else_arm] => Some(else_arm.clone()),
+        // Without an `ELSE`, an unmatched `CASE` yields NULL.
+        _ => Some(ExprImpl::literal_null(func_call.return_type())),
+    }
+}
+
+/// Collects the distinct `InputRef`s of an expression in occurrence order.
+#[derive(Default)]
+struct InputRefCollector {
+    input_refs: Vec<InputRef>,
+}
+
+impl ExprVisitor for InputRefCollector {
+    fn visit_input_ref(&mut self, input_ref: &InputRef) {
+        if !self.input_refs.contains(input_ref) {
+            self.input_refs.push(input_ref.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::types::DataType;
+
+    use super::*;
+
+    /// `(NOT (v1 > 1)) OR (v1 > 1)` over a nullable `v1`.
+    fn tautology_over_nullable_column() -> (ExprImpl, ExprImpl) {
+        let v1: ExprImpl = InputRef::new(0, DataType::Int32).into();
+        let e: ExprImpl = FunctionCall::new(
+            ExprType::GreaterThan,
+            vec![v1.clone(), ExprImpl::literal_int(1)],
+        )
+        .unwrap()
+        .into();
+        let not_e: ExprImpl = FunctionCall::new(ExprType::Not, vec![e.clone()])
+            .unwrap()
+            .into();
+        let pattern = FunctionCall::new(ExprType::Or, vec![not_e, e])
+            .unwrap()
+            .into();
+        (v1, pattern)
+    }
+
+    #[test]
+    fn test_conservative_mode_wraps_is_not_null() {
+        let (v1, pattern) = tautology_over_nullable_column();
+        let rule = StreamFilterExpressionSimplifyRule { aggressive: false };
+        let simplified = rule.simplify_conjunction(&pattern).unwrap();
+        let expected: ExprImpl = FunctionCall::new(ExprType::IsNotNull, vec![v1])
+            .unwrap()
+            .into();
+        assert_eq!(simplified, expected);
+    }
+
+    #[test]
+    fn test_conservative_mode_leaves_non_strict_predicate_unchanged() {
+        // For `e = (v1 IS NULL)`, the predicate `(NOT e) OR e` is constantly true and
+        // never NULL, so rewriting it to `IsNotNull(v1)` would wrongly drop rows with
+        // NULL `v1`. The conservative mode must prove `e` strict and bail out here.
+        let v1: ExprImpl = InputRef::new(0, DataType::Int32).into();
+        let e: ExprImpl = FunctionCall::new(ExprType::IsNull, vec![v1]).unwrap().into();
+        let not_e: ExprImpl = FunctionCall::new(ExprType::Not, vec![e.clone()])
+            .unwrap()
+            .into();
+        let pattern: ExprImpl = FunctionCall::new(ExprType::Or, vec![not_e, e])
+            .unwrap()
+            .into();
+
+        let rule = StreamFilterExpressionSimplifyRule { aggressive: false };
+        assert!(rule.simplify_conjunction(&pattern).is_none());
+
+        // The aggressive mode may still fold it: the predicate is a genuine tautology
+        // regardless of NULLs.
+        let rule = StreamFilterExpressionSimplifyRule { aggressive: true };
+        assert_eq!(
+            rule.simplify_conjunction(&pattern).unwrap(),
+            ExprImpl::literal_bool(true)
+        );
+    }
+
+    #[test]
+    fn test_conservative_mode_handles_multiple_columns() {
+        // `(NOT (v1 > v2)) OR (v1 > v2)` involves two columns; the conservative rewrite
+        // is `IsNotNull(v1) AND IsNotNull(v2)`.
+        let v1: ExprImpl = InputRef::new(0, DataType::Int32).into();
+        let v2: ExprImpl = InputRef::new(1, DataType::Int32).into();
+        let e: ExprImpl = FunctionCall::new(ExprType::GreaterThan, vec![v1.clone(), v2.clone()])
+            .unwrap()
+            .into();
+        let not_e: ExprImpl = FunctionCall::new(ExprType::Not, vec![e.clone()])
+            .unwrap()
+            .into();
+        let pattern = FunctionCall::new(ExprType::Or, vec![not_e, e])
+            .unwrap()
+            .into();
+
+        let rule = StreamFilterExpressionSimplifyRule { aggressive: false };
+        let simplified = rule.simplify_conjunction(&pattern).unwrap();
+        let expected: ExprImpl = FunctionCall::new(
+            ExprType::And,
+            vec![
+                FunctionCall::new(ExprType::IsNotNull, vec![v1]).unwrap().into(),
+                FunctionCall::new(ExprType::IsNotNull, vec![v2]).unwrap().into(),
+            ],
+        )
+        .unwrap()
+        .into();
+        assert_eq!(simplified, expected);
+    }
+
+    #[test]
+    fn test_conservative_mode_requires_strictness_in_every_column() {
+        // `e = (v1 IS NULL) OR (v2 > 1)` is strict in neither `v1` (the IS NULL arm
+        // never yields NULL) nor `v2` (the OR is TRUE whenever `v1` is NULL), so the
+        // multi-column rewrite must not wrap either column with `IS NOT NULL`.
+        let v1: ExprImpl = InputRef::new(0, DataType::Int32).into();
+        let v2: ExprImpl = InputRef::new(1, DataType::Int32).into();
+        let is_null: ExprImpl = FunctionCall::new(ExprType::IsNull, vec![v1]).unwrap().into();
+        let gt: ExprImpl = FunctionCall::new(ExprType::GreaterThan, vec![v2, ExprImpl::literal_int(1)])
+            .unwrap()
+            .into();
+        let e: ExprImpl = FunctionCall::new(ExprType::Or, vec![is_null, gt])
+            .unwrap()
+            .into();
+        let not_e: ExprImpl = FunctionCall::new(ExprType::Not, vec![e.clone()])
+            .unwrap()
+            .into();
+        let pattern: ExprImpl = FunctionCall::new(ExprType::Or, vec![not_e, e])
+            .unwrap()
+            .into();
+
+        let rule = StreamFilterExpressionSimplifyRule { aggressive: false };
+        assert!(rule.simplify_conjunction(&pattern).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rule_fires_above_projection() {
+        use risingwave_common::catalog::{Field, Schema};
+
+        use crate::optimizer::optimizer_context::OptimizerContext;
+        use crate::optimizer::plan_node::{LogicalProject, LogicalValues};
+
+        let ctx = OptimizerContext::mock().await;
+        let values = LogicalValues::new(
+            vec![],
+            Schema {
+                fields: vec![Field::with_name(DataType::Int32, "v1")],
+            },
+            ctx,
+        );
+        let project = LogicalProject::new(
+            values.into(),
+            vec![InputRef::new(0, DataType::Int32).into()],
+        );
+        let (v1, pattern) = tautology_over_nullable_column();
+        let filter: PlanRef = LogicalFilter::new(project.into(), Condition::with_expr(pattern)).into();
+
+        let rule = StreamFilterExpressionSimplifyRule { aggressive: false };
+        let result = rule.apply(filter).unwrap();
+        let simplified = result.as_logical_filter().unwrap();
+        let expected: ExprImpl = FunctionCall::new(ExprType::IsNotNull, vec![v1])
+            .unwrap()
+            .into();
+        assert_eq!(simplified.predicate().conjunctions, vec![expected]);
+    }
+
+    #[test]
+    fn test_special_pattern_is_symmetric() {
+        let rule = StreamFilterExpressionSimplifyRule { aggressive: false };
+        let e: ExprImpl = FunctionCall::new(
+            ExprType::GreaterThan,
+            vec![
+                InputRef::new(0, DataType::Int32).into(),
+                ExprImpl::literal_int(1),
+            ],
+        )
+        .unwrap()
+        .into();
+        // `1 = 1` is const-foldable but not a literal.
+        let one_eq_one: ExprImpl = FunctionCall::new(
+            ExprType::Equal,
+            vec![ExprImpl::literal_int(1), ExprImpl::literal_int(1)],
+        )
+        .unwrap()
+        .into();
+        let not_one_eq_one: ExprImpl = FunctionCall::new(ExprType::Not, vec![one_eq_one.clone()])
+            .unwrap()
+            .into();
+
+        // `e OR (1 = 1)` and `(1 = 1) OR e` both fold to true.
+        for (lhs, rhs) in [(e.clone(), one_eq_one.clone()), (one_eq_one.clone(), e.clone())] {
+            let pattern: ExprImpl = FunctionCall::new(ExprType::Or, vec![lhs, rhs])
+                .unwrap()
+                .into();
+            assert_eq!(
+                rule.simplify_conjunction(&pattern).unwrap(),
+                ExprImpl::literal_bool(true)
+            );
+        }
+
+        // `e AND (NOT (1 = 1))` and the mirrored form both fold to false.
+        for (lhs, rhs) in [
+            (e.clone(), not_one_eq_one.clone()),
+            (not_one_eq_one.clone(), e.clone()),
+        ] {
+            let pattern: ExprImpl = FunctionCall::new(ExprType::And, vec![lhs, rhs])
+                .unwrap()
+                .into();
+            assert_eq!(
+                rule.simplify_conjunction(&pattern).unwrap(),
+                ExprImpl::literal_bool(false)
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_distinct_from_self_folds() {
+        let v1: ExprImpl = InputRef::new(0, DataType::Int32).into();
+        let rule = StreamFilterExpressionSimplifyRule { aggressive: false };
+
+        // `v1 IS NOT DISTINCT FROM v1` is true even for NULL `v1`.
+        let pattern: ExprImpl =
+            FunctionCall::new(ExprType::IsNotDistinctFrom, vec![v1.clone(), v1.clone()])
+                .unwrap()
+                .into();
+        assert_eq!(
+            rule.simplify_conjunction(&pattern).unwrap(),
+            ExprImpl::literal_bool(true)
+        );
+
+        // `v1 IS DISTINCT FROM v1` is false even for NULL `v1`.
+        let pattern: ExprImpl =
+            FunctionCall::new(ExprType::IsDistinctFrom, vec![v1.clone(), v1.clone()])
+                .unwrap()
+                .into();
+        assert_eq!(
+            rule.simplify_conjunction(&pattern).unwrap(),
+            ExprImpl::literal_bool(false)
+        );
+
+        // Structurally different operands are left alone.
+        let v2: ExprImpl = InputRef::new(1, DataType::Int32).into();
+        let pattern: ExprImpl = FunctionCall::new(ExprType::IsDistinctFrom, vec![v1, v2])
+            .unwrap()
+            .into();
+        assert!(rule.simplify_conjunction(&pattern).is_none());
+    }
+
+    #[test]
+    fn test_const_case_collapses_to_selected_branch() {
+        let v1: ExprImpl = InputRef::new(0, DataType::Int32).into();
+        let gt: ExprImpl = FunctionCall::new(
+            ExprType::GreaterThan,
+            vec![v1.clone(), ExprImpl::literal_int(1)],
+        )
+        .unwrap()
+        .into();
+        let lt: ExprImpl = FunctionCall::new(
+            ExprType::LessThan,
+            vec![v1.clone(), ExprImpl::literal_int(0)],
+        )
+        .unwrap()
+        .into();
+        let rule = StreamFilterExpressionSimplifyRule { aggressive: false };
+
+        // `CASE WHEN true THEN v1 > 1 ELSE v1 < 0 END` collapses to `v1 > 1`.
+        let case: ExprImpl = FunctionCall::new(
+            ExprType::Case,
+            vec![ExprImpl::literal_bool(true), gt.clone(), lt.clone()],
+        )
+        .unwrap()
+        .into();
+        assert_eq!(rule.simplify_conjunction(&case).unwrap(), gt);
+
+        // A false condition falls through to the `ELSE` arm.
+        let case: ExprImpl = FunctionCall::new(
+            ExprType::Case,
+            vec![ExprImpl::literal_bool(false), gt.clone(), lt.clone()],
+        )
+        .unwrap()
+        .into();
+        assert_eq!(rule.simplify_conjunction(&case).unwrap(), lt);
+
+        // A non-const condition leaves the expression alone.
+        let case: ExprImpl = FunctionCall::new(ExprType::Case, vec![gt.clone(), lt.clone()])
+            .unwrap()
+            .into();
+        assert!(rule.simplify_conjunction(&case).is_none());
+    }
+
+    #[test]
+    fn test_aggressive_mode_folds_to_true() {
+        let (_, pattern) = tautology_over_nullable_column();
+        let rule = StreamFilterExpressionSimplifyRule { aggressive: true };
+        let simplified = rule.simplify_conjunction(&pattern).unwrap();
+        assert_eq!(simplified, ExprImpl::literal_bool(true));
+    }
+}
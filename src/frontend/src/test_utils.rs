@@ -53,6 +53,7 @@ use risingwave_pb::meta::list_fragment_distribution_response::FragmentDistributi
 use risingwave_pb::meta::list_object_dependencies_response::PbObjectDependencies;
 use risingwave_pb::meta::list_table_fragment_states_response::TableFragmentState;
 use risingwave_pb::meta::list_table_fragments_response::TableFragmentInfo;
+use risingwave_pb::meta::update_worker_node_schedulability_request::Schedulability;
 use risingwave_pb::meta::{EventLog, PbTableParallelism, SystemParams};
 use risingwave_pb::stream_plan::StreamFragmentGraph;
 use risingwave_pb::user::update_user_request::UpdateField;
@@ -978,6 +979,14 @@ impl FrontendMetaClient for MockFrontendMetaClient {
     async fn list_compact_task_progress(&self) -> RpcResult<Vec<CompactTaskProgress>> {
         unimplemented!()
     }
+
+    async fn update_worker_node_schedulability(
+        &self,
+        _worker_ids: Vec<u32>,
+        _schedulability: Schedulability,
+    ) -> RpcResult<()> {
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -19,6 +19,29 @@ use risingwave_pb::hummock::CompactionConfig;
 
 const MAX_LEVEL: u64 = 6;
 
+/// Compression algorithm choices accepted by the per-level `compression_algorithm`
+/// strings of `CompactionConfig`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompressionAlgorithm {
+    None,
+    Lz4,
+    Zstd,
+    /// Only usable with an SST layer built with snappy support; see the `snappy`
+    /// feature of this crate.
+    Snappy,
+}
+
+impl CompressionAlgorithm {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "None",
+            Self::Lz4 => "Lz4",
+            Self::Zstd => "Zstd",
+            Self::Snappy => "Snappy",
+        }
+    }
+}
+
 pub struct CompactionConfigBuilder {
     config: CompactionConfig,
 }
@@ -66,6 +89,9 @@ impl CompactionConfigBuilder {
                     compaction_config::level0_overlapping_sub_level_compact_level_count(),
                 tombstone_reclaim_ratio: compaction_config::tombstone_reclaim_ratio(),
                 enable_emergency_picker: compaction_config::enable_emergency_picker(),
+                // Empty by default: the built-in doubling-every-two-levels heuristic
+                // applies unless a per-level override is configured.
+                target_file_size_multiplier_per_level: vec![],
             },
         }
     }
@@ -94,14 +120,77 @@ impl CompactionConfigBuilder {
             .max_space_reclaim_bytes(opt.max_space_reclaim_bytes)
             .level0_max_compact_file_number(opt.level0_max_compact_file_number)
             .tombstone_reclaim_ratio(opt.tombstone_reclaim_ratio)
+            .enable_emergency_picker(opt.enable_emergency_picker)
+            .compaction_mode(opt.compaction_mode)
+            .split_by_state_table(opt.split_by_state_table)
+            .split_weight_by_vnode(opt.split_weight_by_vnode)
+    }
+
+    /// Sets `max_level` and resizes the per-level vectors (`compression_algorithm`,
+    /// and `target_file_size_multiplier_per_level` when configured) to `max_level + 1`
+    /// so the config stays self-consistent. Growing pads with the last configured
+    /// entry (or `"None"` if the compression vector is empty); shrinking truncates.
+    pub fn max_level(mut self, v: u64) -> Self {
+        self.config.max_level = v;
+        let pad = self
+            .config
+            .compression_algorithm
+            .last()
+            .cloned()
+            .unwrap_or_else(|| "None".to_string());
+        self.config
+            .compression_algorithm
+            .resize(v as usize + 1, pad);
+        if let Some(&pad) = self.config.target_file_size_multiplier_per_level.last() {
+            self.config
+                .target_file_size_multiplier_per_level
+                .resize(v as usize + 1, pad);
+        }
+        self
+    }
+
+    /// Overrides the compression algorithm for a single level. Slot 0 applies to L0 and
+    /// the levels below the base level; slot `k > 0` applies to the `k`-th level above
+    /// the base level. If the vector is shorter than `max_level + 1`, it is first
+    /// extended with `None` so the length invariant checked by
+    /// [`validate_compaction_config`] keeps holding.
+    pub fn compression_for_level(mut self, level: usize, algo: CompressionAlgorithm) -> Self {
+        let expected_len = self.config.max_level as usize + 1;
+        assert!(
+            level < expected_len,
+            "level {} out of range, expect < max_level + 1 = {}",
+            level,
+            expected_len
+        );
+        if self.config.compression_algorithm.len() < expected_len {
+            self.config
+                .compression_algorithm
+                .resize(expected_len, "None".to_string());
+        }
+        self.config.compression_algorithm[level] = algo.as_str().to_string();
+        self
     }
 
     pub fn build(self) -> CompactionConfig {
         if let Err(reason) = validate_compaction_config(&self.config) {
             tracing::warn!("Bad compaction config: {}", reason);
         }
+        if let Err(reason) = validate_compaction_config_shape(&self.config) {
+            tracing::warn!("Degenerate compaction config shape: {}", reason);
+        }
         self.config
     }
+
+    /// Like [`Self::build`], but fails hard on an invalid config instead of logging a
+    /// warning and returning it anyway. A degenerate shape is still only a warning, as
+    /// it merely hints at poor amplification rather than broken compaction.
+    pub fn try_build(self) -> Result<CompactionConfig, String> {
+        validate_compaction_config(&self.config)?;
+        if let Err(reason) = validate_compaction_config_shape(&self.config) {
+            tracing::warn!("Degenerate compaction config shape: {}", reason);
+        }
+        Ok(self.config)
+    }
 }
 
 /// Returns Ok if `config` is valid,
@@ -114,6 +203,168 @@ pub fn validate_compaction_config(config: &CompactionConfig) -> Result<(), Strin
             config.level0_stop_write_threshold_sub_level_number, sub_level_number_threshold_min
         ));
     }
+    if config.level0_max_compact_file_number < config.level0_tier_compact_file_number {
+        return Err(format!(
+            "level0_max_compact_file_number {} is smaller than level0_tier_compact_file_number {}, so tier compaction can never pick enough files",
+            config.level0_max_compact_file_number, config.level0_tier_compact_file_number
+        ));
+    }
+    // See the comment on `level0_max_compact_file_number` in `new()`: a full L0
+    // compaction must be able to produce at least the base level target.
+    if config
+        .level0_max_compact_file_number
+        .saturating_mul(config.target_file_size_base)
+        <= config.max_bytes_for_level_base
+    {
+        return Err(format!(
+            "level0_max_compact_file_number {} * target_file_size_base {} must be larger than max_bytes_for_level_base {}",
+            config.level0_max_compact_file_number,
+            config.target_file_size_base,
+            config.max_bytes_for_level_base
+        ));
+    }
+    // There is one compression algorithm per level plus one for L0, otherwise
+    // `get_compression_algorithm` indexes out of range during compaction.
+    let expected_compression_number = config.max_level as usize + 1;
+    if config.compression_algorithm.len() != expected_compression_number {
+        return Err(format!(
+            "compression_algorithm has {} entries, expect max_level + 1 = {}",
+            config.compression_algorithm.len(),
+            expected_compression_number
+        ));
+    }
+    for (idx, algorithm) in config.compression_algorithm.iter().enumerate() {
+        match algorithm.as_str() {
+            "None" | "Lz4" | "Zstd" => {}
+            "Snappy" => {
+                if !cfg!(feature = "snappy") {
+                    return Err(format!(
+                        "compression algorithm \"Snappy\" at index {} requires an SST layer with snappy support, rebuild with the `snappy` feature",
+                        idx
+                    ));
+                }
+            }
+            _ => {
+                return Err(format!(
+                    "unknown compression algorithm \"{}\" at index {}, expect one of None, Lz4, Zstd, Snappy",
+                    algorithm, idx
+                ));
+            }
+        }
+    }
+    // An empty vector disables the per-level target file size override. When
+    // configured, there is one multiplier per level plus one for L0, with the same
+    // slot convention as `compression_algorithm`.
+    if !config.target_file_size_multiplier_per_level.is_empty() {
+        let expected_multiplier_number = config.max_level as usize + 1;
+        if config.target_file_size_multiplier_per_level.len() != expected_multiplier_number {
+            return Err(format!(
+                "target_file_size_multiplier_per_level has {} entries, expect max_level + 1 = {}",
+                config.target_file_size_multiplier_per_level.len(),
+                expected_multiplier_number
+            ));
+        }
+        if let Some(idx) = config
+            .target_file_size_multiplier_per_level
+            .iter()
+            .position(|&multiplier| multiplier == 0)
+        {
+            return Err(format!(
+                "target_file_size_multiplier_per_level[{}] is 0, the target file size of every level must be non-zero",
+                idx
+            ));
+        }
+        // Deeper levels pack more logical data per byte under heavier compression, so
+        // their target files must not be smaller than those of the levels above.
+        if config
+            .target_file_size_multiplier_per_level
+            .windows(2)
+            .any(|pair| pair[1] < pair[0])
+        {
+            return Err(format!(
+                "target_file_size_multiplier_per_level {:?} must be non-decreasing from L0 to the bottommost level",
+                config.target_file_size_multiplier_per_level
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Validates an online transition from `old` to `new`, for the dynamic
+/// reconfiguration path. Beyond `new` being valid on its own (checked via
+/// [`validate_compaction_config`]), some changes are rejected because they are unsafe
+/// against data written under `old`, even though `new` would be a fine config for a
+/// fresh group. Use [`diff_compaction_config`] to report what would change.
+pub fn validate_compaction_config_transition(
+    old: &CompactionConfig,
+    new: &CompactionConfig,
+) -> Result<(), String> {
+    validate_compaction_config(new)?;
+    // Levels beyond the new `max_level` may already hold data, which no picker would
+    // ever compact away. Growing the tree is safe; shrinking it is not.
+    if new.max_level < old.max_level {
+        return Err(format!(
+            "cannot shrink max_level from {} to {}: the dropped levels may already hold data",
+            old.max_level, new.max_level
+        ));
+    }
+    // The overlap strategy of existing level handlers is derived from the compaction
+    // mode at group creation, so it must not change over the group's lifetime.
+    if new.compaction_mode != old.compaction_mode {
+        return Err(format!(
+            "cannot change compaction_mode from {} to {} on a live group",
+            old.compaction_mode, new.compaction_mode
+        ));
+    }
+    // These determine how the key space of the group is split into SSTs; existing
+    // files were cut under the old setting and would violate the new one.
+    if new.split_by_state_table != old.split_by_state_table {
+        return Err("cannot change split_by_state_table on a live group".to_string());
+    }
+    if new.split_weight_by_vnode != old.split_weight_by_vnode {
+        return Err(format!(
+            "cannot change split_weight_by_vnode from {} to {} on a live group",
+            old.split_weight_by_vnode, new.split_weight_by_vnode
+        ));
+    }
+    Ok(())
+}
+
+/// The minimal reasonable fan-out between adjacent levels.
+const MIN_LEVEL_MULTIPLIER: u64 = 2;
+/// The minimal implied capacity of the bottommost level. The target of level `k` is
+/// `max_bytes_for_level_base * max_bytes_for_level_multiplier^(k - 1)`; if the
+/// bottommost target stays below this bound, typical data sizes won't fit in the tree
+/// without excessive read/write amplification.
+const MIN_IMPLIED_BOTTOM_LEVEL_BYTES: u64 = 512 * 1024 * 1024 * 1024;
+
+/// Checks that the relationship between `max_bytes_for_level_base`,
+/// `max_bytes_for_level_multiplier` and `max_level` forms a reasonable LSM shape.
+/// Returns the reason when the shape is degenerate. Unlike
+/// [`validate_compaction_config`], a violation here is not a hard error but a strong
+/// hint of a misconfiguration that only manifests as poor amplification.
+pub fn validate_compaction_config_shape(config: &CompactionConfig) -> Result<(), String> {
+    if config.max_bytes_for_level_multiplier < MIN_LEVEL_MULTIPLIER {
+        return Err(format!(
+            "max_bytes_for_level_multiplier {} is too small for a leveled LSM, expect >= {}",
+            config.max_bytes_for_level_multiplier, MIN_LEVEL_MULTIPLIER
+        ));
+    }
+    let implied_bottom_level_bytes = config.max_bytes_for_level_base.saturating_mul(
+        config
+            .max_bytes_for_level_multiplier
+            .saturating_pow(config.max_level.saturating_sub(1) as u32),
+    );
+    if implied_bottom_level_bytes < MIN_IMPLIED_BOTTOM_LEVEL_BYTES {
+        return Err(format!(
+            "the implied bottommost level target is only {} bytes ({} * {}^{}), expect >= {} bytes; increase max_level or max_bytes_for_level_multiplier",
+            implied_bottom_level_bytes,
+            config.max_bytes_for_level_base,
+            config.max_bytes_for_level_multiplier,
+            config.max_level.saturating_sub(1),
+            MIN_IMPLIED_BOTTOM_LEVEL_BYTES
+        ));
+    }
     Ok(())
 }
 
@@ -124,22 +375,120 @@ impl Default for CompactionConfigBuilder {
 }
 
 macro_rules! builder_field {
-    ($( $name:ident: $type:ty ),* ,) => {
+    // Fields marked `=> manual` have a hand-written setter elsewhere but stay in the
+    // list so `diff_compaction_config`/`enumerate_compaction_config` still cover them.
+    (@setter $name:ident: $type:ty => manual) => {};
+    (@setter $name:ident: $type:ty) => {
+        pub fn $name(mut self, v: $type) -> Self {
+            self.config.$name = v;
+            self
+        }
+    };
+    ($( $name:ident: $type:ty $(=> $manual:ident)? ),* ,) => {
         impl CompactionConfigBuilder {
             $(
-                pub fn $name(mut self, v:$type) -> Self {
-                    self.config.$name = v;
-                    self
+                builder_field!(@setter $name: $type $(=> $manual)?);
+            )*
+        }
+
+        /// Returns the builder-settable fields whose values differ between `old` and
+        /// `new`, with their stringified old/new values.
+        pub fn diff_compaction_config(
+            old: &CompactionConfig,
+            new: &CompactionConfig,
+        ) -> Vec<FieldChange> {
+            let mut changes = Vec::new();
+            $(
+                if old.$name != new.$name {
+                    changes.push(FieldChange {
+                        field: stringify!($name),
+                        old: format!("{:?}", old.$name),
+                        new: format!("{:?}", new.$name),
+                    });
                 }
             )*
+            changes
+        }
+
+        /// Returns all builder-settable fields of `config` with their stringified
+        /// current values, in the order they are declared. Generated from the same
+        /// field list as the builder, so it cannot drift from the actual config.
+        pub fn enumerate_compaction_config(config: &CompactionConfig) -> Vec<(&'static str, String)> {
+            vec![
+                $(
+                    (stringify!($name), format!("{:?}", config.$name)),
+                )*
+            ]
         }
     }
 }
 
+/// A single changed field of a compaction config, with stringified old/new values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub field: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+/// A record of one applied compaction config change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactionConfigAuditEntry {
+    pub group_id: u64,
+    /// Unix timestamp in milliseconds when the change was applied.
+    pub applied_at_ms: u64,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Number of recent compaction config changes kept in memory for inspection.
+const COMPACTION_CONFIG_AUDIT_CAPACITY: usize = 32;
+
+/// Keeps the last [`COMPACTION_CONFIG_AUDIT_CAPACITY`] applied compaction config
+/// changes and emits a structured log entry for each, so operators can correlate
+/// config changes with performance shifts.
+#[derive(Default)]
+pub struct CompactionConfigAuditLog {
+    entries: std::collections::VecDeque<CompactionConfigAuditEntry>,
+}
+
+impl CompactionConfigAuditLog {
+    /// Records a config change applied to a group. Returns the recorded entry, or
+    /// `None` if the two configs don't differ in any builder-settable field.
+    pub fn record(
+        &mut self,
+        group_id: u64,
+        old: &CompactionConfig,
+        new: &CompactionConfig,
+    ) -> Option<&CompactionConfigAuditEntry> {
+        let changes = diff_compaction_config(old, new);
+        if changes.is_empty() {
+            return None;
+        }
+        tracing::info!(group_id, changes = ?changes, "compaction config updated");
+        if self.entries.len() >= COMPACTION_CONFIG_AUDIT_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(CompactionConfigAuditEntry {
+            group_id,
+            applied_at_ms: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("system clock set before UNIX epoch")
+                .as_millis() as u64,
+            changes,
+        });
+        self.entries.back()
+    }
+
+    /// The recorded entries, oldest first.
+    pub fn entries(&self) -> impl Iterator<Item = &CompactionConfigAuditEntry> {
+        self.entries.iter()
+    }
+}
+
 builder_field! {
     max_bytes_for_level_base: u64,
     max_bytes_for_level_multiplier: u64,
-    max_level: u64,
+    max_level: u64 => manual,
     max_compaction_bytes: u64,
     sub_level_max_compaction_bytes: u64,
     level0_tier_compact_file_number: u64,
@@ -154,4 +503,355 @@ builder_field! {
     level0_sub_level_compact_level_count: u32,
     level0_overlapping_sub_level_compact_level_count: u32,
     tombstone_reclaim_ratio: u32,
+    enable_emergency_picker: bool,
+    split_by_state_table: bool,
+    split_weight_by_vnode: u32,
+    target_file_size_multiplier_per_level: Vec<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_opt_reflects_all_fields() {
+        // Every field of `CompactionConfigOpt` is carried into the built config.
+        // Exhaustive construction (no `..Default::default()`) makes this fail to
+        // compile when a new opt field is added but not threaded through `with_opt`.
+        let opt = CompactionConfigOpt {
+            max_bytes_for_level_base: 1,
+            max_bytes_for_level_multiplier: 2,
+            max_compaction_bytes: 3,
+            sub_level_max_compaction_bytes: 4,
+            level0_tier_compact_file_number: 5,
+            target_file_size_base: 6,
+            compaction_filter_mask: 7,
+            max_sub_compaction: 8,
+            level0_stop_write_threshold_sub_level_number: 9,
+            level0_sub_level_compact_level_count: 10,
+            level0_overlapping_sub_level_compact_level_count: 11,
+            max_space_reclaim_bytes: 12,
+            level0_max_compact_file_number: 13,
+            tombstone_reclaim_ratio: 14,
+            enable_emergency_picker: false,
+            compaction_mode: 1,
+            split_by_state_table: true,
+            split_weight_by_vnode: 15,
+        };
+        let config = CompactionConfigBuilder::with_opt(&opt).build();
+        assert_eq!(config.max_bytes_for_level_base, 1);
+        assert_eq!(config.max_bytes_for_level_multiplier, 2);
+        assert_eq!(config.max_compaction_bytes, 3);
+        assert_eq!(config.sub_level_max_compaction_bytes, 4);
+        assert_eq!(config.level0_tier_compact_file_number, 5);
+        assert_eq!(config.target_file_size_base, 6);
+        assert_eq!(config.compaction_filter_mask, 7);
+        assert_eq!(config.max_sub_compaction, 8);
+        assert_eq!(config.level0_stop_write_threshold_sub_level_number, 9);
+        assert_eq!(config.level0_sub_level_compact_level_count, 10);
+        assert_eq!(config.level0_overlapping_sub_level_compact_level_count, 11);
+        assert_eq!(config.max_space_reclaim_bytes, 12);
+        assert_eq!(config.level0_max_compact_file_number, 13);
+        assert_eq!(config.tombstone_reclaim_ratio, 14);
+        assert!(!config.enable_emergency_picker);
+        assert_eq!(config.compaction_mode, 1);
+        assert!(config.split_by_state_table);
+        assert_eq!(config.split_weight_by_vnode, 15);
+    }
+
+    #[test]
+    fn test_validate_compaction_config_shape() {
+        // The default config has a reasonable shape.
+        let config = CompactionConfigBuilder::new().build();
+        assert!(validate_compaction_config_shape(&config).is_ok());
+
+        // Too little fan-out for the configured number of levels: the bottommost level
+        // target stays tiny.
+        let degenerate = CompactionConfigBuilder::new()
+            .max_bytes_for_level_base(64 * 1024 * 1024)
+            .max_bytes_for_level_multiplier(2)
+            .max_level(3)
+            .build();
+        assert!(validate_compaction_config_shape(&degenerate).is_err());
+
+        // A multiplier below 2 degenerates into near-equal level sizes.
+        let degenerate = CompactionConfigBuilder::new()
+            .max_bytes_for_level_multiplier(1)
+            .build();
+        assert!(validate_compaction_config_shape(&degenerate).is_err());
+    }
+
+    #[test]
+    fn test_try_build() {
+        assert!(CompactionConfigBuilder::new().try_build().is_ok());
+
+        let err = CompactionConfigBuilder::new()
+            .level0_stop_write_threshold_sub_level_number(0)
+            .try_build()
+            .unwrap_err();
+        assert!(
+            err.contains("level0_stop_write_threshold_sub_level_number"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_validate_level0_thresholds() {
+        let config = CompactionConfigBuilder::new().build();
+        assert!(validate_compaction_config(&config).is_ok());
+
+        // level0_max_compact_file_number must not fall below the tier threshold.
+        let contradictory = CompactionConfigBuilder::new()
+            .level0_tier_compact_file_number(100)
+            .level0_max_compact_file_number(10)
+            .build();
+        let err = validate_compaction_config(&contradictory).unwrap_err();
+        assert!(err.contains("level0_tier_compact_file_number"), "{}", err);
+
+        // A full L0 compaction must cover at least the base level target.
+        let undersized = CompactionConfigBuilder::new()
+            .level0_max_compact_file_number(2)
+            .target_file_size_base(1024)
+            .build();
+        let err = validate_compaction_config(&undersized).unwrap_err();
+        assert!(err.contains("max_bytes_for_level_base"), "{}", err);
+    }
+
+    #[test]
+    fn test_validate_compression_algorithm() {
+        // The default config pairs one algorithm per level plus one for L0.
+        let config = CompactionConfigBuilder::new().build();
+        assert!(validate_compaction_config(&config).is_ok());
+
+        // A vector not matching `max_level + 1` would index out of range.
+        let mut truncated = config.clone();
+        truncated.compression_algorithm.pop();
+        let err = validate_compaction_config(&truncated).unwrap_err();
+        assert!(err.contains("compression_algorithm"), "{}", err);
+
+        // Unknown algorithm strings are rejected.
+        let mut unknown = config.clone();
+        unknown.compression_algorithm[3] = "Snappy".to_string();
+        let err = validate_compaction_config(&unknown).unwrap_err();
+        assert!(err.contains("Snappy"), "{}", err);
+    }
+
+    #[test]
+    fn test_max_level_resizes_compression_algorithm() {
+        // Shrinking truncates the per-level compression vector.
+        let config = CompactionConfigBuilder::new().max_level(4).build();
+        assert_eq!(
+            config.compression_algorithm,
+            vec!["None", "None", "None", "Lz4", "Lz4"]
+        );
+        assert!(validate_compaction_config(&config).is_ok());
+
+        // Growing pads with the last configured algorithm.
+        let config = CompactionConfigBuilder::with_config(config)
+            .max_level(6)
+            .build();
+        assert_eq!(
+            config.compression_algorithm,
+            vec!["None", "None", "None", "Lz4", "Lz4", "Lz4", "Lz4"]
+        );
+        assert!(validate_compaction_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_compression_for_level() {
+        // "Use Zstd only on the bottom level" is a one-liner.
+        let config = CompactionConfigBuilder::new()
+            .compression_for_level(6, CompressionAlgorithm::Zstd)
+            .compression_for_level(5, CompressionAlgorithm::Lz4)
+            .build();
+        assert_eq!(config.compression_algorithm[6], "Zstd");
+        assert_eq!(config.compression_algorithm[5], "Lz4");
+        assert!(validate_compaction_config(&config).is_ok());
+
+        // A too-short vector is extended with "None" up to `max_level + 1`.
+        let mut short = CompactionConfigBuilder::new().build();
+        short.compression_algorithm.truncate(2);
+        let config = CompactionConfigBuilder::with_config(short)
+            .compression_for_level(6, CompressionAlgorithm::Zstd)
+            .build();
+        assert_eq!(
+            config.compression_algorithm,
+            vec!["None", "None", "None", "None", "None", "None", "Zstd"]
+        );
+        assert!(validate_compaction_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_validate_target_file_size_multiplier_per_level() {
+        // An empty vector keeps the built-in heuristic and is always valid.
+        let config = CompactionConfigBuilder::new().build();
+        assert!(config.target_file_size_multiplier_per_level.is_empty());
+        assert!(validate_compaction_config(&config).is_ok());
+
+        // A non-decreasing vector of `max_level + 1` non-zero multipliers is valid.
+        let config = CompactionConfigBuilder::new()
+            .target_file_size_multiplier_per_level(vec![1, 1, 1, 2, 2, 4, 8])
+            .build();
+        assert!(validate_compaction_config(&config).is_ok());
+
+        // The vector must match `max_level + 1`, like `compression_algorithm`.
+        let truncated = CompactionConfigBuilder::new()
+            .target_file_size_multiplier_per_level(vec![1, 2, 4])
+            .build();
+        let err = validate_compaction_config(&truncated).unwrap_err();
+        assert!(err.contains("max_level + 1"), "{}", err);
+
+        // A zero multiplier would produce zero-sized target files.
+        let zeroed = CompactionConfigBuilder::new()
+            .target_file_size_multiplier_per_level(vec![1, 1, 0, 2, 2, 4, 8])
+            .build();
+        let err = validate_compaction_config(&zeroed).unwrap_err();
+        assert!(err.contains("non-zero"), "{}", err);
+
+        // Targets must not shrink towards the bottom of the tree.
+        let shrinking = CompactionConfigBuilder::new()
+            .target_file_size_multiplier_per_level(vec![1, 4, 2, 2, 2, 4, 8])
+            .build();
+        let err = validate_compaction_config(&shrinking).unwrap_err();
+        assert!(err.contains("non-decreasing"), "{}", err);
+
+        // `max_level` keeps the vector in sync, padding with the last multiplier.
+        let config = CompactionConfigBuilder::new()
+            .target_file_size_multiplier_per_level(vec![1, 1, 1, 2, 2, 4, 8])
+            .max_level(4)
+            .build();
+        assert_eq!(config.target_file_size_multiplier_per_level, vec![1, 1, 1, 2, 2]);
+        assert!(validate_compaction_config(&config).is_ok());
+        let config = CompactionConfigBuilder::with_config(config).max_level(6).build();
+        assert_eq!(
+            config.target_file_size_multiplier_per_level,
+            vec![1, 1, 1, 2, 2, 2, 2]
+        );
+        assert!(validate_compaction_config(&config).is_ok());
+    }
+
+    #[test]
+    fn test_snappy_compression() {
+        // The CPU-cheaper Snappy codec for a mid level is a one-liner, but only
+        // validates when built with snappy support.
+        let config = CompactionConfigBuilder::new()
+            .compression_for_level(3, CompressionAlgorithm::Snappy)
+            .build();
+        assert_eq!(config.compression_algorithm[3], "Snappy");
+        let result = validate_compaction_config(&config);
+        if cfg!(feature = "snappy") {
+            assert!(result.is_ok());
+        } else {
+            let err = result.unwrap_err();
+            assert!(err.contains("snappy"), "{}", err);
+        }
+    }
+
+    #[test]
+    fn test_enumerate_compaction_config() {
+        let config = CompactionConfigBuilder::new().build();
+        let fields = enumerate_compaction_config(&config);
+
+        // All builder-settable fields are enumerated, in declaration order.
+        assert_eq!(
+            fields.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+            vec![
+                "max_bytes_for_level_base",
+                "max_bytes_for_level_multiplier",
+                "max_level",
+                "max_compaction_bytes",
+                "sub_level_max_compaction_bytes",
+                "level0_tier_compact_file_number",
+                "compaction_mode",
+                "compression_algorithm",
+                "compaction_filter_mask",
+                "target_file_size_base",
+                "max_sub_compaction",
+                "max_space_reclaim_bytes",
+                "level0_stop_write_threshold_sub_level_number",
+                "level0_max_compact_file_number",
+                "level0_sub_level_compact_level_count",
+                "level0_overlapping_sub_level_compact_level_count",
+                "tombstone_reclaim_ratio",
+                "enable_emergency_picker",
+                "split_by_state_table",
+                "split_weight_by_vnode",
+                "target_file_size_multiplier_per_level",
+            ]
+        );
+
+        // Values are the stringified current values.
+        let (_, max_level) = fields.iter().find(|(name, _)| *name == "max_level").unwrap();
+        assert_eq!(*max_level, format!("{:?}", config.max_level));
+    }
+
+    #[test]
+    fn test_validate_compaction_config_transition() {
+        let old = CompactionConfigBuilder::new().max_level(4).build();
+
+        // Tuning thresholds or growing the tree is a safe live change.
+        let tuned = CompactionConfigBuilder::with_config(old.clone())
+            .max_compaction_bytes(old.max_compaction_bytes * 2)
+            .max_level(6)
+            .build();
+        assert!(validate_compaction_config_transition(&old, &tuned).is_ok());
+
+        // Shrinking `max_level` would strand data in the dropped levels.
+        let shrunk = CompactionConfigBuilder::with_config(old.clone())
+            .max_level(2)
+            .build();
+        let err = validate_compaction_config_transition(&old, &shrunk).unwrap_err();
+        assert!(err.contains("max_level"), "{}", err);
+
+        // Changing how the key space is split is rejected on a live group.
+        let resplit = CompactionConfigBuilder::with_config(old.clone())
+            .split_weight_by_vnode(8)
+            .build();
+        let err = validate_compaction_config_transition(&old, &resplit).unwrap_err();
+        assert!(err.contains("split_weight_by_vnode"), "{}", err);
+
+        // The new config must be valid on its own, too.
+        let invalid = CompactionConfigBuilder::with_config(old.clone())
+            .level0_stop_write_threshold_sub_level_number(0)
+            .build();
+        let err = validate_compaction_config_transition(&old, &invalid).unwrap_err();
+        assert!(
+            err.contains("level0_stop_write_threshold_sub_level_number"),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_audit_log_records_changed_fields() {
+        let old = CompactionConfigBuilder::new().build();
+        let new = CompactionConfigBuilder::with_config(old.clone())
+            .max_bytes_for_level_base(old.max_bytes_for_level_base + 1)
+            .max_sub_compaction(old.max_sub_compaction + 1)
+            .build();
+
+        let mut audit_log = CompactionConfigAuditLog::default();
+        let entry = audit_log.record(42, &old, &new).unwrap().clone();
+        assert_eq!(entry.group_id, 42);
+        assert_eq!(
+            entry.changes,
+            vec![
+                FieldChange {
+                    field: "max_bytes_for_level_base",
+                    old: format!("{:?}", old.max_bytes_for_level_base),
+                    new: format!("{:?}", new.max_bytes_for_level_base),
+                },
+                FieldChange {
+                    field: "max_sub_compaction",
+                    old: format!("{:?}", old.max_sub_compaction),
+                    new: format!("{:?}", new.max_sub_compaction),
+                },
+            ]
+        );
+
+        // Applying an identical config records nothing.
+        assert!(audit_log.record(42, &new, &new).is_none());
+        assert_eq!(audit_log.entries().count(), 1);
+    }
 }
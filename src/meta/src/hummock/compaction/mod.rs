@@ -171,19 +171,8 @@ pub fn create_compaction_task(
     base_level: usize,
     compaction_task_type: compact_task::TaskType,
 ) -> CompactionTask {
-    let target_file_size = if input.target_level == 0 {
-        compaction_config.target_file_size_base
-    } else if input.target_level == base_level {
-        // This is just a temporary optimization measure. We hope to reduce the size of SST as much
-        // as possible to reduce the amount of data blocked by a single task during compaction,
-        // but too many files will increase computing overhead.
-        // TODO: remove it after can reduce configuration `target_file_size_base`.
-        compaction_config.target_file_size_base / 4
-    } else {
-        assert!(input.target_level >= base_level);
-        let step = (input.target_level - base_level) / 2;
-        compaction_config.target_file_size_base << step
-    };
+    let target_file_size =
+        target_file_size_for_level(compaction_config, base_level, input.target_level);
 
     CompactionTask {
         compression_algorithm: get_compression_algorithm(
@@ -198,6 +187,43 @@ pub fn create_compaction_task(
     }
 }
 
+/// Target size of the files produced into `target_level`. When
+/// `target_file_size_multiplier_per_level` is configured, the size is
+/// `target_file_size_base` scaled by the multiplier of the level, with the same slot
+/// convention as `compression_algorithm`; otherwise a built-in heuristic doubles the
+/// base size every two levels beyond the base level.
+pub fn target_file_size_for_level(
+    compaction_config: &CompactionConfig,
+    base_level: usize,
+    target_level: usize,
+) -> u64 {
+    let multipliers = &compaction_config.target_file_size_multiplier_per_level;
+    if !multipliers.is_empty() {
+        let idx = if target_level == 0 || target_level < base_level {
+            0
+        } else {
+            target_level - base_level + 1
+        };
+        return compaction_config
+            .target_file_size_base
+            .saturating_mul(multipliers[idx]);
+    }
+
+    if target_level == 0 {
+        compaction_config.target_file_size_base
+    } else if target_level == base_level {
+        // This is just a temporary optimization measure. We hope to reduce the size of SST as much
+        // as possible to reduce the amount of data blocked by a single task during compaction,
+        // but too many files will increase computing overhead.
+        // TODO: remove it after can reduce configuration `target_file_size_base`.
+        compaction_config.target_file_size_base / 4
+    } else {
+        assert!(target_level >= base_level);
+        let step = (target_level - base_level) / 2;
+        compaction_config.target_file_size_base << step
+    }
+}
+
 pub fn get_compression_algorithm(
     compaction_config: &CompactionConfig,
     base_level: usize,
@@ -236,3 +262,30 @@ impl Default for CompactionDeveloperConfig {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hummock::compaction::compaction_config::CompactionConfigBuilder;
+
+    #[test]
+    fn test_target_file_size_for_level() {
+        // Built-in heuristic: the base size for L0, a quarter of it into the base
+        // level, then doubling every two levels.
+        let config = CompactionConfigBuilder::new().target_file_size_base(32).build();
+        assert_eq!(target_file_size_for_level(&config, 4, 0), 32);
+        assert_eq!(target_file_size_for_level(&config, 4, 4), 8);
+        assert_eq!(target_file_size_for_level(&config, 4, 5), 32);
+        assert_eq!(target_file_size_for_level(&config, 4, 6), 64);
+
+        // Per-level multipliers replace the heuristic, using the same slot convention
+        // as `compression_algorithm`.
+        let config = CompactionConfigBuilder::with_config(config)
+            .target_file_size_multiplier_per_level(vec![1, 1, 2, 4, 8, 16, 32])
+            .build();
+        assert_eq!(target_file_size_for_level(&config, 4, 0), 32);
+        assert_eq!(target_file_size_for_level(&config, 4, 4), 32);
+        assert_eq!(target_file_size_for_level(&config, 4, 5), 64);
+        assert_eq!(target_file_size_for_level(&config, 4, 6), 128);
+    }
+}
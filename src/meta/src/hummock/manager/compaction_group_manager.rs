@@ -41,7 +41,7 @@ use tracing::warn;
 use super::write_lock;
 use crate::controller::SqlMetaStore;
 use crate::hummock::compaction::compaction_config::{
-    validate_compaction_config, CompactionConfigBuilder,
+    validate_compaction_config, CompactionConfigAuditLog, CompactionConfigBuilder,
 };
 use crate::hummock::error::{Error, Result};
 use crate::hummock::manager::{
@@ -64,7 +64,9 @@ impl HummockManager {
     ) -> Result<RwLock<CompactionGroupManager>> {
         let default_config = match env.opts.compaction_config.as_ref() {
             None => CompactionConfigBuilder::new().build(),
-            Some(opt) => CompactionConfigBuilder::with_opt(opt).build(),
+            Some(opt) => CompactionConfigBuilder::with_opt(opt)
+                .try_build()
+                .map_err(Error::CompactionGroup)?,
         };
         Self::build_compaction_group_manager_with_config(env, default_config).await
     }
@@ -77,6 +79,7 @@ impl HummockManager {
             compaction_groups: BTreeMap::new(),
             default_config,
             sql_meta_store: env.sql_meta_store(),
+            audit_log: CompactionConfigAuditLog::default(),
         });
         compaction_group_manager
             .write()
@@ -776,6 +779,8 @@ pub(super) struct CompactionGroupManager {
     compaction_groups: BTreeMap<CompactionGroupId, CompactionGroup>,
     default_config: CompactionConfig,
     sql_meta_store: Option<SqlMetaStore>,
+    /// Audit log of recently applied compaction config changes.
+    audit_log: CompactionConfigAuditLog,
 }
 
 impl CompactionGroupManager {
@@ -867,6 +872,7 @@ impl CompactionGroupManager {
             BTreeMapTransaction::new(&mut self.compaction_groups,)
         );
         let mut result = Vec::with_capacity(compaction_group_ids.len());
+        let mut audit_entries = Vec::with_capacity(compaction_group_ids.len());
         for compaction_group_id in compaction_group_ids.iter().unique() {
             let group = compaction_groups.get(compaction_group_id).ok_or_else(|| {
                 Error::CompactionGroup(format!("invalid group {}", *compaction_group_id))
@@ -877,11 +883,24 @@ impl CompactionGroupManager {
                 return Err(Error::CompactionGroup(reason));
             }
             let mut new_group = group.clone();
+            let old_config = new_group.compaction_config.clone();
             new_group.compaction_config = Arc::new(config);
             compaction_groups.insert(*compaction_group_id, new_group.clone());
+            audit_entries.push((
+                *compaction_group_id,
+                old_config,
+                new_group.compaction_config.clone(),
+            ));
             result.push(new_group);
         }
         commit_multi_var!(meta_store, self.sql_meta_store, compaction_groups)?;
+        // Only record the changes once the transaction is committed, so that a failed
+        // commit (or an invalid group later in the loop) leaves no audit entries
+        // claiming the change was applied.
+        for (compaction_group_id, old_config, new_config) in audit_entries {
+            self.audit_log
+                .record(compaction_group_id, &old_config, &new_config);
+        }
         Ok(result)
     }
 
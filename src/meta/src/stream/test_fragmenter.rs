@@ -73,6 +73,7 @@ fn make_sum_aggcall(idx: u32) -> AggCall {
         distinct: false,
         order_by: vec![],
         filter: None,
+        ignore_nulls: false,
         direct_args: vec![],
     }
 }
@@ -25,6 +25,92 @@ impl TempoGen {
 
         let max_bytes_per_trace = config.max_bytes_per_trace;
 
+        let otlp_http_receiver = config
+            .otlp_http_port
+            .map(|otlp_http_port| {
+                format!(
+                    r#"
+          http:
+            endpoint: "{otlp_host}:{otlp_http_port}""#
+                )
+            })
+            .unwrap_or_default();
+
+        let (metrics_generator_section, metrics_generator_overrides) =
+            if config.enable_metrics_generator {
+                let wal_path = config
+                    .storage_path
+                    .as_ref()
+                    .map(|path| format!("{path}/generator/wal"))
+                    .unwrap_or_else(|| "/tmp/tempo/generator/wal".to_string());
+
+                let remote_write = config
+                    .metrics_generator_remote_write_url
+                    .as_ref()
+                    .map(|url| {
+                        format!(
+                            r#"
+    remote_write:
+      - url: "{url}"
+        send_exemplars: true"#
+                        )
+                    })
+                    .unwrap_or_default();
+
+                let section = format!(
+                    r#"
+metrics_generator:
+  registry:
+    external_labels:
+      source: tempo
+  storage:
+    path: "{wal_path}"{remote_write}
+"#
+                );
+
+                let overrides = r#"
+    # Processors enabled for the metrics-generator
+    metrics_generator:
+      processors: [service-graphs, span-metrics]
+"#
+                .to_string();
+
+                (section, overrides)
+            } else {
+                (String::new(), String::new())
+            };
+
+        // Leave the blocks out entirely when unset so that Tempo's own defaults apply.
+        let compactor_section = config
+            .retention_hours
+            .map(|retention_hours| {
+                format!(
+                    r#"
+compactor:
+  compaction:
+    # Duration to keep trace blocks before deletion
+    block_retention: {retention_hours}h
+"#
+                )
+            })
+            .unwrap_or_default();
+
+        let storage_section = config
+            .storage_path
+            .as_ref()
+            .map(|storage_path| {
+                format!(
+                    r#"
+storage:
+  trace:
+    backend: local
+    local:
+      path: "{storage_path}"
+"#
+                )
+            })
+            .unwrap_or_default();
+
         format!(
             r#"# --- THIS FILE IS AUTO GENERATED BY RISEDEV ---
 server:
@@ -38,7 +124,7 @@ distributor:
       otlp:
         protocols:
           grpc:
-            endpoint: "{otlp_host}:{otlp_port}"
+            endpoint: "{otlp_host}:{otlp_port}"{otlp_http_receiver}
 
 # Overrides configuration block
 overrides:
@@ -57,7 +143,41 @@ overrides:
       # During ingestion, exceeding the threshold results in errors like
       #    TRACE_TOO_LARGE: max size of trace (5000000) exceeded while adding 387 bytes
       max_bytes_per_trace: {max_bytes_per_trace}
-"#
+{metrics_generator_overrides}{compactor_section}{storage_section}{metrics_generator_section}"#
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(otlp_http_port: Option<u16>) -> TempoConfig {
+        let mut yaml = r#"
+id: tempo
+listen-address: "127.0.0.1"
+address: "127.0.0.1"
+port: 3200
+otlp-port: 4317
+max-bytes-per-trace: 5000000
+enable-metrics-generator: false
+"#
+        .to_string();
+        if let Some(port) = otlp_http_port {
+            yaml.push_str(&format!("otlp-http-port: {port}\n"));
+        }
+        serde_yaml::from_str(&yaml).unwrap()
+    }
+
+    #[test]
+    fn test_otlp_http_receiver() {
+        let yml = TempoGen.gen_tempo_yml(&config(None));
+        assert!(yml.contains(r#"endpoint: "127.0.0.1:4317""#));
+        assert!(!yml.contains("http:"));
+
+        let yml = TempoGen.gen_tempo_yml(&config(Some(4318)));
+        assert!(yml.contains(r#"endpoint: "127.0.0.1:4317""#));
+        assert!(yml.contains("http:"));
+        assert!(yml.contains(r#"endpoint: "127.0.0.1:4318""#));
+    }
+}
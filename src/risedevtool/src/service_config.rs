@@ -223,7 +223,12 @@ pub struct TempoConfig {
     pub address: String,
     pub port: u16,
     pub otlp_port: u16,
+    pub otlp_http_port: Option<u16>,
     pub max_bytes_per_trace: usize,
+    pub retention_hours: Option<u64>,
+    pub storage_path: Option<String>,
+    pub enable_metrics_generator: bool,
+    pub metrics_generator_remote_write_url: Option<String>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -27,7 +27,8 @@ use risingwave_pb::compute::config_service_client::ConfigServiceClient;
 use risingwave_pb::compute::{ShowConfigRequest, ShowConfigResponse};
 use risingwave_pb::monitor_service::monitor_service_client::MonitorServiceClient;
 use risingwave_pb::monitor_service::{
-    AnalyzeHeapRequest, AnalyzeHeapResponse, GetBackPressureRequest, GetBackPressureResponse,
+    AnalyzeHeapRequest, AnalyzeHeapResponse, DrainHummockEventsRequest,
+    DrainHummockEventsResponse, GetBackPressureRequest, GetBackPressureResponse,
     HeapProfilingRequest, HeapProfilingResponse, ListHeapProfilingRequest,
     ListHeapProfilingResponse, ProfilingRequest, ProfilingResponse, StackTraceRequest,
     StackTraceResponse,
@@ -198,6 +199,15 @@ impl ComputeClient {
             .into_inner())
     }
 
+    pub async fn drain_hummock_events(&self) -> Result<DrainHummockEventsResponse> {
+        Ok(self
+            .monitor_client
+            .to_owned()
+            .drain_hummock_events(DrainHummockEventsRequest::default())
+            .await?
+            .into_inner())
+    }
+
     pub async fn get_back_pressure(&self) -> Result<GetBackPressureResponse> {
         Ok(self
             .monitor_client
@@ -21,7 +21,8 @@ use risingwave_pb::compactor::{
 };
 use risingwave_pb::monitor_service::monitor_service_server::MonitorService;
 use risingwave_pb::monitor_service::{
-    AnalyzeHeapRequest, AnalyzeHeapResponse, GetBackPressureRequest, GetBackPressureResponse,
+    AnalyzeHeapRequest, AnalyzeHeapResponse, DrainHummockEventsRequest,
+    DrainHummockEventsResponse, GetBackPressureRequest, GetBackPressureResponse,
     HeapProfilingRequest, HeapProfilingResponse, ListHeapProfilingRequest,
     ListHeapProfilingResponse, ProfilingRequest, ProfilingResponse, StackTraceRequest,
     StackTraceResponse,
@@ -143,4 +144,13 @@ impl MonitorService for MonitorServiceImpl {
             "Get Back Pressure unimplemented in compactor",
         ))
     }
+
+    async fn drain_hummock_events(
+        &self,
+        _request: Request<DrainHummockEventsRequest>,
+    ) -> Result<Response<DrainHummockEventsResponse>, Status> {
+        Err(Status::unimplemented(
+            "Drain hummock events unimplemented in compactor",
+        ))
+    }
 }
@@ -0,0 +1,374 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use risingwave_common::buffer::Bitmap;
+use risingwave_pb::hummock::{PbChangeLogShard, PbEpochNewChangeLog, PbTableChangeLog, SstableInfo};
+
+use crate::HummockEpoch;
+
+/// The change log of a subset of vnodes of a table written in some epochs.
+///
+/// The `new_value` SSTs store the value of the changed keys after the change, while
+/// the `old_value` SSTs store the value before the change. A key that only appears
+/// in `new_value` is an insert, a key that only appears in `old_value` is a delete,
+/// and a key that appears in both is an update.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangeLogShard {
+    pub vnode_bitmap: Arc<Bitmap>,
+    pub new_value: Vec<SstableInfo>,
+    pub old_value: Vec<SstableInfo>,
+}
+
+impl ChangeLogShard {
+    pub fn to_protobuf(&self) -> PbChangeLogShard {
+        PbChangeLogShard {
+            vnode_bitmap: Some(self.vnode_bitmap.to_protobuf()),
+            new_value: self.new_value.clone(),
+            old_value: self.old_value.clone(),
+        }
+    }
+
+    /// Returns an error instead of panicking on a malformed protobuf, e.g. one with a
+    /// missing vnode bitmap persisted by an older version.
+    pub fn from_protobuf(pb: &PbChangeLogShard) -> Result<Self, String> {
+        let vnode_bitmap = pb
+            .vnode_bitmap
+            .as_ref()
+            .ok_or_else(|| "change log shard misses vnode bitmap".to_string())?;
+        Ok(Self {
+            vnode_bitmap: Arc::new(Bitmap::from(vnode_bitmap)),
+            new_value: pb.new_value.clone(),
+            old_value: pb.old_value.clone(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EpochNewChangeLog {
+    pub shards: Vec<ChangeLogShard>,
+    pub epochs: Vec<HummockEpoch>,
+}
+
+impl EpochNewChangeLog {
+    pub fn to_protobuf(&self) -> PbEpochNewChangeLog {
+        PbEpochNewChangeLog {
+            shards: self.shards.iter().map(ChangeLogShard::to_protobuf).collect(),
+            epochs: self.epochs.clone(),
+        }
+    }
+
+    pub fn from_protobuf(pb: &PbEpochNewChangeLog) -> Result<Self, String> {
+        Ok(Self {
+            shards: pb
+                .shards
+                .iter()
+                .map(ChangeLogShard::from_protobuf)
+                .collect::<Result<_, _>>()?,
+            epochs: pb.epochs.clone(),
+        })
+    }
+
+    /// Merges a change log of later epochs into this one. Epochs are concatenated, and
+    /// the SST lists of shards covering exactly the same vnodes are combined with the
+    /// earlier SSTs first, preserving epoch ordering. Shards with different vnode
+    /// bitmaps are kept separate, since their SSTs cover different key spaces.
+    fn merge(&mut self, later: EpochNewChangeLog) {
+        self.epochs.extend(later.epochs);
+        for shard in later.shards {
+            match self
+                .shards
+                .iter_mut()
+                .find(|s| s.vnode_bitmap == shard.vnode_bitmap)
+            {
+                Some(existing) => {
+                    existing.new_value.extend(shard.new_value);
+                    existing.old_value.extend(shard.old_value);
+                }
+                None => self.shards.push(shard),
+            }
+        }
+    }
+
+    /// Returns the shards relevant to a reader that owns the vnodes in `vnodes`, i.e.
+    /// those whose `vnode_bitmap` intersects it. A scaled-down reader should use this
+    /// to avoid fetching SSTs it would discard.
+    pub fn shards_for_vnodes<'a>(
+        &'a self,
+        vnodes: &'a Bitmap,
+    ) -> impl Iterator<Item = &'a ChangeLogShard> + 'a {
+        self.shards
+            .iter()
+            .filter(move |shard| (&*shard.vnode_bitmap & vnodes).count_ones() > 0)
+    }
+}
+
+/// Change logs of a state table written in multiple epochs. Epochs are sorted in
+/// ascending order, which means earlier epoch at the front.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TableChangeLog(pub Vec<EpochNewChangeLog>);
+
+impl TableChangeLog {
+    pub fn to_protobuf(&self) -> PbTableChangeLog {
+        PbTableChangeLog {
+            change_logs: self.0.iter().map(EpochNewChangeLog::to_protobuf).collect(),
+        }
+    }
+
+    pub fn from_protobuf(pb: &PbTableChangeLog) -> Result<Self, String> {
+        Ok(Self(
+            pb.change_logs
+                .iter()
+                .map(EpochNewChangeLog::from_protobuf)
+                .collect::<Result<_, _>>()?,
+        ))
+    }
+
+    /// Returns the change logs that cover any epoch in `[min_epoch, max_epoch]`.
+    pub fn filter_epoch(&self, (min_epoch, max_epoch): (u64, u64)) -> &[EpochNewChangeLog] {
+        let start = self.0.partition_point(|epoch_change_log| {
+            *epoch_change_log.epochs.last().expect("non-empty") < min_epoch
+        });
+        let end = self.0.partition_point(|epoch_change_log| {
+            *epoch_change_log.epochs.first().expect("non-empty") <= max_epoch
+        });
+        &self.0[start..end]
+    }
+
+    /// Merges consecutive change logs so that at most `target_epoch_count` entries
+    /// remain. One entry is accumulated per sync, each with a tiny SST list, which
+    /// bloats the version over time; compacting them gives readers fewer, larger
+    /// entries while preserving epoch ordering.
+    pub fn compact(&mut self, target_epoch_count: usize) {
+        if target_epoch_count == 0 || self.0.len() <= target_epoch_count {
+            return;
+        }
+        // Distribute the entries over `target_epoch_count` consecutive groups as evenly
+        // as possible and merge each group into a single entry.
+        let total = self.0.len();
+        let mut iter = std::mem::take(&mut self.0).into_iter();
+        let mut compacted = Vec::with_capacity(target_epoch_count);
+        for group_idx in 0..target_epoch_count {
+            let group_size =
+                total * (group_idx + 1) / target_epoch_count - total * group_idx / target_epoch_count;
+            let mut merged = iter.next().expect("group is non-empty");
+            for _ in 1..group_size {
+                merged.merge(iter.next().expect("group is non-empty"));
+            }
+            compacted.push(merged);
+        }
+        self.0 = compacted;
+    }
+
+    /// Drops the change logs whose epochs are all below the retention bound
+    /// `min_epoch`, i.e. keeps only the logs still readable at `min_epoch` or above.
+    pub fn truncate(&mut self, min_epoch: u64) {
+        self.0
+            .retain(|change_log| *change_log.epochs.last().expect("non-empty") >= min_epoch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rand::{thread_rng, Rng};
+    use risingwave_common::buffer::{Bitmap, BitmapBuilder};
+    use risingwave_pb::hummock::{PbChangeLogShard, SstableInfo};
+
+    use crate::change_log::{ChangeLogShard, EpochNewChangeLog, TableChangeLog};
+
+    fn table_change_log(epochs: impl IntoIterator<Item = Vec<u64>>) -> TableChangeLog {
+        TableChangeLog(
+            epochs
+                .into_iter()
+                .map(|epochs| EpochNewChangeLog {
+                    shards: vec![],
+                    epochs,
+                })
+                .collect(),
+        )
+    }
+
+    fn vnode_bitmap(vnodes: impl IntoIterator<Item = usize>) -> Arc<Bitmap> {
+        let mut builder = BitmapBuilder::zeroed(256);
+        for vnode in vnodes {
+            builder.set(vnode, true);
+        }
+        Arc::new(builder.finish())
+    }
+
+    fn shard(vnodes: impl IntoIterator<Item = usize>) -> ChangeLogShard {
+        ChangeLogShard {
+            vnode_bitmap: vnode_bitmap(vnodes),
+            new_value: vec![],
+            old_value: vec![],
+        }
+    }
+
+    #[test]
+    fn test_protobuf_roundtrip() {
+        let mut rng = thread_rng();
+        let change_log = TableChangeLog(
+            (0..rng.gen_range(1..8))
+                .map(|i| EpochNewChangeLog {
+                    shards: (0..rng.gen_range(0..4))
+                        .map(|_| ChangeLogShard {
+                            vnode_bitmap: vnode_bitmap(
+                                (0..256).filter(|_| rng.gen_bool(0.5)),
+                            ),
+                            new_value: (0..rng.gen_range(0..3))
+                                .map(|_| SstableInfo {
+                                    object_id: rng.gen(),
+                                    sst_id: rng.gen(),
+                                    ..Default::default()
+                                })
+                                .collect(),
+                            old_value: (0..rng.gen_range(0..3))
+                                .map(|_| SstableInfo {
+                                    object_id: rng.gen(),
+                                    sst_id: rng.gen(),
+                                    ..Default::default()
+                                })
+                                .collect(),
+                        })
+                        .collect(),
+                    epochs: vec![i * 65536 + 65536],
+                })
+                .collect(),
+        );
+
+        let pb = change_log.to_protobuf();
+        assert_eq!(TableChangeLog::from_protobuf(&pb), Ok(change_log));
+    }
+
+    #[test]
+    fn test_from_protobuf_missing_bitmap() {
+        // A protobuf with a missing vnode bitmap must not panic the node.
+        let pb = PbChangeLogShard {
+            vnode_bitmap: None,
+            new_value: vec![],
+            old_value: vec![],
+        };
+        assert!(ChangeLogShard::from_protobuf(&pb)
+            .unwrap_err()
+            .contains("vnode bitmap"));
+    }
+
+    #[test]
+    fn test_shards_for_vnodes() {
+        let change_log = EpochNewChangeLog {
+            shards: vec![shard(0..64), shard(64..128), shard(128..256)],
+            epochs: vec![65536],
+        };
+
+        // A reader owning vnodes [32, 96) overlaps the first two shards only.
+        let vnodes = vnode_bitmap(32..96);
+        let matched: Vec<_> = change_log.shards_for_vnodes(&vnodes).collect();
+        assert_eq!(matched.len(), 2);
+        assert_eq!(matched[0], &change_log.shards[0]);
+        assert_eq!(matched[1], &change_log.shards[1]);
+
+        // A reader owning all vnodes reads all shards.
+        let vnodes = vnode_bitmap(0..256);
+        assert_eq!(change_log.shards_for_vnodes(&vnodes).count(), 3);
+
+        // A bitmap disjoint from all shards matches nothing.
+        let vnodes = vnode_bitmap([]);
+        assert_eq!(change_log.shards_for_vnodes(&vnodes).count(), 0);
+    }
+
+    #[test]
+    fn test_filter_epoch() {
+        let table_change_log = table_change_log([vec![2], vec![3, 4], vec![5]]);
+
+        // A range below all epochs matches nothing.
+        assert!(table_change_log.filter_epoch((0, 1)).is_empty());
+        // A range above all epochs matches nothing.
+        assert!(table_change_log.filter_epoch((6, 7)).is_empty());
+
+        // A change log written in multiple epochs is matched when the range covers any
+        // of them.
+        assert_eq!(table_change_log.filter_epoch((3, 3)), &table_change_log.0[1..2]);
+        assert_eq!(table_change_log.filter_epoch((4, 4)), &table_change_log.0[1..2]);
+
+        // Ranges spanning multiple change logs.
+        assert_eq!(table_change_log.filter_epoch((2, 4)), &table_change_log.0[0..2]);
+        assert_eq!(table_change_log.filter_epoch((1, 6)), &table_change_log.0[..]);
+    }
+
+    #[test]
+    fn test_compact() {
+        fn sst(id: u64) -> SstableInfo {
+            SstableInfo {
+                object_id: id,
+                sst_id: id,
+                ..Default::default()
+            }
+        }
+        let entry = |epoch: u64, vnodes: std::ops::Range<usize>, sst_id: u64| EpochNewChangeLog {
+            shards: vec![ChangeLogShard {
+                vnode_bitmap: vnode_bitmap(vnodes),
+                new_value: vec![sst(sst_id)],
+                old_value: vec![sst(sst_id + 100)],
+            }],
+            epochs: vec![epoch],
+        };
+        let mut log = TableChangeLog(vec![
+            entry(1, 0..128, 1),
+            entry(2, 0..128, 2),
+            entry(3, 128..256, 3),
+            entry(4, 0..128, 4),
+        ]);
+
+        log.compact(2);
+        assert_eq!(log.0.len(), 2);
+
+        // Epochs 1 and 2 cover the same vnodes, so their shards are combined with the
+        // earlier SSTs first.
+        assert_eq!(log.0[0].epochs, vec![1, 2]);
+        assert_eq!(log.0[0].shards.len(), 1);
+        assert_eq!(log.0[0].shards[0].new_value, vec![sst(1), sst(2)]);
+        assert_eq!(log.0[0].shards[0].old_value, vec![sst(101), sst(102)]);
+
+        // Epochs 3 and 4 cover disjoint vnodes, so their shards stay separate.
+        assert_eq!(log.0[1].epochs, vec![3, 4]);
+        assert_eq!(log.0[1].shards.len(), 2);
+        assert_eq!(log.0[1].shards[0].new_value, vec![sst(3)]);
+        assert_eq!(log.0[1].shards[1].new_value, vec![sst(4)]);
+
+        // Already at or below the target: no-op.
+        let before = log.clone();
+        log.compact(2);
+        assert_eq!(log, before);
+    }
+
+    #[test]
+    fn test_truncate() {
+        let mut log = table_change_log([vec![2], vec![3, 4], vec![5]]);
+
+        // A bound below all epochs drops nothing.
+        log.truncate(2);
+        assert_eq!(log, table_change_log([vec![2], vec![3, 4], vec![5]]));
+
+        // A change log is dropped only when all its epochs are below the bound.
+        log.truncate(4);
+        assert_eq!(log, table_change_log([vec![3, 4], vec![5]]));
+
+        // A bound above all epochs drops everything.
+        log.truncate(6);
+        assert_eq!(log, table_change_log([]));
+    }
+}
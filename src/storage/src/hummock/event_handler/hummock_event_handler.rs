@@ -12,21 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::ops::DerefMut;
 use std::pin::pin;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use arc_swap::ArcSwap;
 use await_tree::InstrumentAwait;
 use itertools::Itertools;
 use parking_lot::RwLock;
 use prometheus::core::{AtomicU64, GenericGauge};
+use risingwave_common::catalog::TableId;
 use risingwave_hummock_sdk::compaction_group::hummock_version_ext::SstDeltaInfo;
 use risingwave_hummock_sdk::{HummockEpoch, LocalSstableInfo};
 use thiserror_ext::AsReport;
 use tokio::spawn;
-use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio::sync::mpsc::{
+    channel, unbounded_channel, Receiver, Sender, UnboundedReceiver, UnboundedSender,
+};
 use tokio::sync::oneshot;
 use tracing::{debug, error, info, trace, warn};
 
@@ -41,8 +45,8 @@ use crate::hummock::event_handler::uploader::{
     UploadTaskInfo, UploadTaskPayload, UploaderEvent,
 };
 use crate::hummock::event_handler::{
-    HummockEvent, HummockReadVersionRef, HummockVersionUpdate, ReadOnlyReadVersionMapping,
-    ReadOnlyRwLockRef,
+    HummockEvent, HummockEventSender, HummockReadVersionRef, HummockVersionUpdate,
+    ReadOnlyReadVersionMapping, ReadOnlyRwLockRef,
 };
 use crate::hummock::local_version::pinned_version::PinnedVersion;
 use crate::hummock::store::version::{
@@ -56,6 +60,10 @@ use crate::monitor::HummockStateStoreMetrics;
 use crate::opts::StorageOpts;
 use crate::store::SyncResult;
 
+/// How long a graceful shutdown waits for in-flight upload tasks to finish before
+/// giving up and acknowledging the shutdown anyway.
+const SHUTDOWN_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[derive(Clone)]
 pub struct BufferTracker {
     flush_threshold: usize,
@@ -119,10 +127,31 @@ impl BufferTracker {
 }
 
 pub struct HummockEventHandler {
+    /// Channel for control events. Unbounded so that control events are never starved
+    /// by a congested data channel.
     hummock_event_tx: UnboundedSender<HummockEvent>,
     hummock_event_rx: UnboundedReceiver<HummockEvent>,
+    /// Bounded channel for high-volume data events, providing backpressure to writers
+    /// when the event handler falls behind. See `hummock_event_channel_capacity`.
+    hummock_data_event_tx: Sender<HummockEvent>,
+    hummock_data_event_rx: Receiver<HummockEvent>,
     version_update_rx: UnboundedReceiver<HummockVersionUpdate>,
-    pending_sync_requests: BTreeMap<HummockEpoch, oneshot::Sender<HummockResult<SyncResult>>>,
+    /// Pending sync requests, with the time each request was registered, to detect
+    /// requests that have been pending longer than `sync_epoch_timeout`.
+    pending_sync_requests: BTreeMap<
+        HummockEpoch,
+        (
+            tokio::time::Instant,
+            oneshot::Sender<HummockResult<SyncResult>>,
+        ),
+    >,
+    /// When set, pending sync requests that exceed this timeout are answered with an
+    /// error instead of waiting forever. `None` when `hummock_sync_timeout_sec` is 0.
+    sync_epoch_timeout: Option<Duration>,
+    /// Number of registered read version instances per table above which further
+    /// registrations are logged as a suspected leak. See
+    /// `max_read_version_instances_per_table` in the storage config.
+    max_read_version_instances_per_table: usize,
     read_version_mapping: Arc<RwLock<ReadVersionMappingType>>,
     /// A copy of `read_version_mapping` but owned by event handler
     local_read_version_mapping: HashMap<LocalInstanceId, HummockReadVersionRef>,
@@ -137,6 +166,8 @@ pub struct HummockEventHandler {
     last_instance_id: LocalInstanceId,
 
     sstable_object_id_manager: Option<Arc<SstableObjectIdManager>>,
+
+    state_store_metrics: Arc<HummockStateStoreMetrics>,
 }
 
 async fn flush_imms(
@@ -209,6 +240,8 @@ impl HummockEventHandler {
         spawn_refill_task: SpawnRefillTask,
     ) -> Self {
         let (hummock_event_tx, hummock_event_rx) = unbounded_channel();
+        let (hummock_data_event_tx, hummock_data_event_rx) =
+            channel(storage_opts.hummock_event_channel_capacity);
         let (version_update_notifier_tx, _) =
             tokio::sync::watch::channel(pinned_version.max_committed_epoch());
         let version_update_notifier_tx = Arc::new(version_update_notifier_tx);
@@ -220,7 +253,7 @@ impl HummockEventHandler {
         let write_conflict_detector = ConflictDetector::new_from_config(storage_opts);
 
         let uploader = HummockUploader::new(
-            state_store_metrics,
+            state_store_metrics.clone(),
             pinned_version.clone(),
             spawn_upload_task,
             spawn_merging_task,
@@ -236,8 +269,14 @@ impl HummockEventHandler {
         Self {
             hummock_event_tx,
             hummock_event_rx,
+            hummock_data_event_tx,
+            hummock_data_event_rx,
             version_update_rx,
             pending_sync_requests: Default::default(),
+            sync_epoch_timeout: (storage_opts.hummock_sync_timeout_sec > 0)
+                .then(|| Duration::from_secs(storage_opts.hummock_sync_timeout_sec)),
+            max_read_version_instances_per_table: storage_opts
+                .max_read_version_instances_per_table,
             version_update_notifier_tx,
             pinned_version: Arc::new(ArcSwap::from_pointee(pinned_version)),
             write_conflict_detector,
@@ -247,6 +286,7 @@ impl HummockEventHandler {
             refiller,
             last_instance_id: 0,
             sstable_object_id_manager,
+            state_store_metrics,
         }
     }
 
@@ -262,8 +302,11 @@ impl HummockEventHandler {
         ReadOnlyRwLockRef::new(self.read_version_mapping.clone())
     }
 
-    pub fn event_sender(&self) -> UnboundedSender<HummockEvent> {
-        self.hummock_event_tx.clone()
+    pub fn event_sender(&self) -> HummockEventSender {
+        HummockEventSender::new(
+            self.hummock_event_tx.clone(),
+            self.hummock_data_event_tx.clone(),
+        )
     }
 
     pub fn buffer_tracker(&self) -> &BufferTracker {
@@ -305,7 +348,7 @@ impl HummockEventHandler {
                 // The smallest pending sync epoch has not synced yet. Wait later
                 break;
             }
-            let (pending_sync_epoch, result_sender) =
+            let (pending_sync_epoch, (_, result_sender)) =
                 self.pending_sync_requests.pop_first().expect("must exist");
             if pending_sync_epoch == epoch {
                 send_sync_result(result_sender, to_sync_result(result));
@@ -387,9 +430,9 @@ impl HummockEventHandler {
 
         // If the epoch is not synced, we add to the `pending_sync_requests` anyway. If the epoch is
         // not a checkpoint epoch, it will be clear with the max synced epoch bumps up.
-        if let Some(old_sync_result_sender) = self
+        if let Some((_, old_sync_result_sender)) = self
             .pending_sync_requests
-            .insert(new_sync_epoch, sync_result_sender)
+            .insert(new_sync_epoch, (tokio::time::Instant::now(), sync_result_sender))
         {
             let _ = old_sync_result_sender
                 .send(Err(HummockError::other(
@@ -404,6 +447,70 @@ impl HummockEventHandler {
         }
     }
 
+    /// The deadline of the earliest registered pending sync request, or `None` when the
+    /// timeout is disabled or there is no pending request.
+    fn next_pending_sync_deadline(&self) -> Option<tokio::time::Instant> {
+        let timeout = self.sync_epoch_timeout?;
+        self.pending_sync_requests
+            .values()
+            .map(|(registered_at, _)| *registered_at + timeout)
+            .min()
+    }
+
+    fn handle_sync_timeout(&mut self) {
+        let timeout = self.sync_epoch_timeout.expect("checked by caller");
+        let now = tokio::time::Instant::now();
+        let timed_out = self
+            .pending_sync_requests
+            .extract_if(|_, (registered_at, _)| *registered_at + timeout <= now)
+            .collect_vec();
+        for (epoch, (_, result_sender)) in timed_out {
+            let message = format!(
+                "sync epoch {} has been pending for more than {:?}. Outstanding uploads: {}",
+                epoch,
+                timeout,
+                self.uploader.describe_outstanding_uploads(),
+            );
+            warn!("{}", message);
+            send_sync_result(result_sender, Err(HummockError::other(message)));
+        }
+    }
+
+    /// Answer all still-pending sync requests with a cancellation error and wait for
+    /// in-flight upload tasks to drain, bounded by [`SHUTDOWN_DRAIN_TIMEOUT`], before
+    /// acknowledging the optional shutdown notifier.
+    async fn handle_shutdown(&mut self, notifier: Option<oneshot::Sender<()>>) {
+        for (epoch, (_, result_sender)) in self.pending_sync_requests.extract_if(|_, _| true) {
+            send_sync_result(
+                result_sender,
+                Err(HummockError::other(format!(
+                    "the sync epoch {} is cancelled: the storage is shutting down",
+                    epoch
+                ))),
+            );
+        }
+
+        let deadline = tokio::time::Instant::now() + SHUTDOWN_DRAIN_TIMEOUT;
+        while self.uploader.has_inflight_upload_tasks() {
+            match tokio::time::timeout_at(deadline, self.uploader.next_event()).await {
+                Ok(event) => self.handle_uploader_event(event),
+                Err(_) => {
+                    warn!(
+                        "shutdown timed out waiting for uploads to drain. Outstanding uploads: {}",
+                        self.uploader.describe_outstanding_uploads()
+                    );
+                    break;
+                }
+            }
+        }
+
+        if let Some(notifier) = notifier {
+            let _ = notifier.send(()).inspect_err(|e| {
+                error!("failed to notify completion of shutdown: {:?}", e);
+            });
+        }
+    }
+
     async fn handle_clear(&mut self, notifier: oneshot::Sender<()>, prev_epoch: u64) {
         info!(
             prev_epoch,
@@ -472,7 +579,7 @@ impl HummockEventHandler {
             );
         }
 
-        for (epoch, result_sender) in self.pending_sync_requests.extract_if(|_, _| true) {
+        for (epoch, (_, result_sender)) in self.pending_sync_requests.extract_if(|_, _| true) {
             send_sync_result(
                 result_sender,
                 Err(HummockError::other(format!(
@@ -504,6 +611,30 @@ impl HummockEventHandler {
         info!(prev_epoch, "clear finished");
     }
 
+    /// Clear the buffered data, read versions and local instances of the given tables
+    /// only. Unlike [`Self::handle_clear`], this does not reset uploader epochs or wait
+    /// for a version update, so other tables keep running undisturbed.
+    fn handle_clear_tables(&mut self, table_ids: HashSet<TableId>, notifier: oneshot::Sender<()>) {
+        info!(?table_ids, "handle clear tables event");
+
+        self.uploader.clear_tables(&table_ids);
+
+        {
+            let mut read_version_mapping_guard = self.read_version_mapping.write();
+            for table_id in &table_ids {
+                if let Some(instances) = read_version_mapping_guard.remove(table_id) {
+                    for instance_id in instances.keys() {
+                        self.local_read_version_mapping.remove(instance_id);
+                    }
+                }
+            }
+        }
+
+        let _ = notifier.send(()).inspect_err(|e| {
+            error!("failed to notify completion of clear tables event: {:?}", e);
+        });
+    }
+
     fn handle_version_update(&mut self, version_payload: HummockVersionUpdate) {
         let pinned_version = self
             .refiller
@@ -602,7 +733,21 @@ impl HummockEventHandler {
 impl HummockEventHandler {
     pub async fn start_hummock_event_handler_worker(mut self) {
         loop {
+            // Refreshed once per processed event rather than on send, so a stuck
+            // handler still reports the depth it last saw before blocking.
+            self.state_store_metrics.event_handler_pending_event.set(
+                (self.hummock_event_rx.len() + self.hummock_data_event_rx.len()) as i64,
+            );
+            let next_sync_deadline = self.next_pending_sync_deadline();
             tokio::select! {
+                _ = async {
+                    match next_sync_deadline {
+                        Some(deadline) => tokio::time::sleep_until(deadline).await,
+                        None => futures::future::pending::<()>().await,
+                    }
+                } => {
+                    self.handle_sync_timeout();
+                }
                 event = self.uploader.next_event() => {
                     self.handle_uploader_event(event);
                 }
@@ -614,17 +759,32 @@ impl HummockEventHandler {
                     let Some(event) = event else { break };
                     match event {
                         HummockEvent::Clear(notifier, prev_epoch) => {
+                            self.state_store_metrics
+                                .event_handler_on_event_counts
+                                .with_label_values(&["Clear"])
+                                .inc();
                             self.handle_clear(notifier, prev_epoch).await
                         },
-                        HummockEvent::Shutdown => {
+                        HummockEvent::Shutdown(notifier) => {
+                            self.state_store_metrics
+                                .event_handler_on_event_counts
+                                .with_label_values(&["Shutdown"])
+                                .inc();
+                            self.handle_shutdown(notifier).await;
                             info!("event handler shutdown");
                             return;
                         },
                         event => {
-                            self.handle_hummock_event(event);
+                            self.handle_hummock_event_with_metrics(event);
                         }
                     }
                 }
+                event = pin!(self.hummock_data_event_rx.recv()) => {
+                    // The handler itself holds a `hummock_data_event_tx`, so the data
+                    // channel can never be closed before the worker exits.
+                    let event = event.expect("data channel should not be closed");
+                    self.handle_hummock_event_with_metrics(event);
+                }
                 version_update = pin!(self.version_update_rx.recv()) => {
                     let Some(version_update) = version_update else {
                         warn!("version update stream ends. event handle shutdown");
@@ -668,6 +828,23 @@ impl HummockEventHandler {
         }
     }
 
+    fn handle_hummock_event_with_metrics(&mut self, event: HummockEvent) {
+        // Only the synchronous handler portion is measured here:
+        // for events like `AwaitSyncEpoch` this is the time to
+        // register the request, not the async completion.
+        let start_time = Instant::now();
+        let event_type = event.category();
+        self.handle_hummock_event(event);
+        self.state_store_metrics
+            .event_handler_on_event_counts
+            .with_label_values(&[event_type])
+            .inc();
+        self.state_store_metrics
+            .event_handler_latency
+            .with_label_values(&[event_type])
+            .observe(start_time.elapsed().as_secs_f64());
+    }
+
     /// Gracefully shutdown if returns `true`.
     fn handle_hummock_event(&mut self, event: HummockEvent) {
         match event {
@@ -683,10 +860,19 @@ impl HummockEventHandler {
             HummockEvent::Clear(_, _) => {
                 unreachable!("clear is handled in separated async context")
             }
-            HummockEvent::Shutdown => {
+            HummockEvent::ClearTables {
+                table_ids,
+                notifier,
+            } => {
+                self.handle_clear_tables(table_ids, notifier);
+            }
+            HummockEvent::Shutdown(_) => {
                 unreachable!("shutdown is handled specially")
             }
-            HummockEvent::ImmToUploader(imm) => {
+            HummockEvent::ImmToUploader {
+                imm,
+                flush_priority,
+            } => {
                 assert!(
                     self.local_read_version_mapping
                         .contains_key(&imm.instance_id),
@@ -694,7 +880,7 @@ impl HummockEventHandler {
                     imm.instance_id,
                     imm.table_id,
                 );
-                self.uploader.add_imm(imm);
+                self.uploader.add_imm(imm, flush_priority);
                 self.uploader.may_flush();
             }
 
@@ -702,6 +888,17 @@ impl HummockEventHandler {
                 epoch,
                 is_checkpoint,
             } => {
+                // The barrier path may deliver a duplicate or stale seal on retry.
+                // Sealing is monotonic, so ignore it instead of reprocessing it, which
+                // would trip the monotonicity assertion in the uploader.
+                if epoch <= self.uploader.max_sealed_epoch() {
+                    debug!(
+                        epoch,
+                        max_sealed_epoch = self.uploader.max_sealed_epoch(),
+                        "ignore duplicate or stale SealEpoch"
+                    );
+                    return;
+                }
                 self.uploader.seal_epoch(epoch);
 
                 if is_checkpoint {
@@ -730,8 +927,29 @@ impl HummockEventHandler {
                 }
             }
 
+            HummockEvent::DrainPending(notifier) => {
+                // Snapshot the backlog length first so that data events enqueued while
+                // draining are left for the normal loop, keeping the drain bounded
+                // under continuous event influx.
+                let backlog = self.hummock_data_event_rx.len();
+                for _ in 0..backlog {
+                    let Ok(event) = self.hummock_data_event_rx.try_recv() else {
+                        break;
+                    };
+                    self.handle_hummock_event(event);
+                }
+                let _ = notifier.send(()).inspect_err(|e| {
+                    error!("unable to send drain pending result: {:?}", e);
+                });
+            }
+
             #[cfg(any(test, feature = "test"))]
             HummockEvent::FlushEvent(sender) => {
+                // `FlushEvent` acts as a barrier over both channels: also drain the data
+                // events that were already enqueued before acknowledging.
+                while let Ok(event) = self.hummock_data_event_rx.try_recv() {
+                    self.handle_hummock_event(event);
+                }
                 let _ = sender.send(()).inspect_err(|e| {
                     error!("unable to send flush result: {:?}", e);
                 });
@@ -765,10 +983,21 @@ impl HummockEventHandler {
                         .insert(instance_id, basic_read_version.clone());
                     let mut read_version_mapping_guard = self.read_version_mapping.write();
 
-                    read_version_mapping_guard
-                        .entry(table_id)
-                        .or_default()
-                        .insert(instance_id, basic_read_version.clone());
+                    let table_instances = read_version_mapping_guard.entry(table_id).or_default();
+                    table_instances.insert(instance_id, basic_read_version.clone());
+                    if table_instances.len() > self.max_read_version_instances_per_table {
+                        // The cap is far above any normal parallelism, so reaching it
+                        // almost certainly means instances are registered without being
+                        // destroyed. The registration itself is not rejected; log
+                        // loudly to catch the leak early.
+                        error!(
+                            "table {} has {} registered read version instances, exceeding the cap of {}; \
+                            read version instances are likely leaking",
+                            table_id,
+                            table_instances.len(),
+                            self.max_read_version_instances_per_table,
+                        );
+                    }
                 }
 
                 match new_read_version_sender.send((
@@ -797,14 +1026,19 @@ impl HummockEventHandler {
                     "read version deregister: table_id: {}, instance_id: {}",
                     table_id, instance_id
                 );
-                self.local_read_version_mapping
+                if self
+                    .local_read_version_mapping
                     .remove(&instance_id)
-                    .unwrap_or_else(|| {
-                        panic!(
-                            "DestroyHummockInstance inexist instance table_id {} instance_id {}",
-                            table_id, instance_id
-                        )
-                    });
+                    .is_none()
+                {
+                    // The instance may have been removed by a `ClearTables` event before
+                    // the local instance guard is dropped.
+                    debug!(
+                        "DestroyHummockInstance instance already cleared: table_id {} instance_id {}",
+                        table_id, instance_id
+                    );
+                    return;
+                }
                 let mut read_version_mapping_guard = self.read_version_mapping.write();
                 let entry = read_version_mapping_guard
                     .get_mut(&table_id)
@@ -983,7 +1217,12 @@ mod tests {
         read_version
             .write()
             .update(VersionUpdate::Staging(StagingData::ImmMem(imm1.clone())));
-        tx.send(HummockEvent::ImmToUploader(imm1.clone())).unwrap();
+        tx.send_data(HummockEvent::ImmToUploader {
+            imm: imm1.clone(),
+            flush_priority: None,
+        })
+        .await
+        .unwrap();
         tx.send(HummockEvent::SealEpoch {
             epoch: epoch1,
             is_checkpoint: true,
@@ -1009,7 +1248,12 @@ mod tests {
             read_version
                 .write()
                 .update(VersionUpdate::Staging(StagingData::ImmMem(imm.clone())));
-            tx.send(HummockEvent::ImmToUploader(imm)).unwrap();
+            tx.send_data(HummockEvent::ImmToUploader {
+                imm,
+                flush_priority: None,
+            })
+            .await
+            .unwrap();
         }
 
         for (staging_imm, imm_id) in read_version
@@ -1071,6 +1315,205 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_duplicate_seal_epoch() {
+        let epoch0 = test_epoch(233);
+        let pinned_version = PinnedVersion::new(
+            HummockVersion::from_rpc_protobuf(&PbHummockVersion {
+                id: 1,
+                max_committed_epoch: epoch0,
+                ..Default::default()
+            }),
+            unbounded_channel().0,
+        );
+
+        let (_version_update_tx, version_update_rx) = unbounded_channel();
+        let event_handler = HummockEventHandler::new_inner(
+            version_update_rx,
+            pinned_version,
+            None,
+            mock_sstable_store(),
+            Arc::new(HummockStateStoreMetrics::unused()),
+            &default_opts_for_test(),
+            Arc::new(|_, _| unreachable!("should not spawn upload task")),
+            Arc::new(|_, _, _, _| unreachable!("should not spawn merging task")),
+            CacheRefiller::default_spawn_refill_task(),
+        );
+
+        let tx = event_handler.event_sender();
+        let _join_handle = spawn(event_handler.start_hummock_event_handler_worker());
+
+        let epoch1 = epoch0.next_epoch();
+        tx.send(HummockEvent::SealEpoch {
+            epoch: epoch1,
+            is_checkpoint: false,
+        })
+        .unwrap();
+        // A duplicate seal of the same epoch and a stale seal of an older epoch are
+        // no-ops. Before the idempotency check, either of them would trip the
+        // monotonicity assertion in the uploader and kill the worker.
+        tx.send(HummockEvent::SealEpoch {
+            epoch: epoch1,
+            is_checkpoint: false,
+        })
+        .unwrap();
+        tx.send(HummockEvent::SealEpoch {
+            epoch: epoch0,
+            is_checkpoint: false,
+        })
+        .unwrap();
+
+        // The worker is still alive and keeps processing events in order.
+        let (flush_tx, flush_rx) = oneshot::channel();
+        tx.send(HummockEvent::FlushEvent(flush_tx)).unwrap();
+        flush_rx.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_drain_pending() {
+        let epoch0 = test_epoch(233);
+        let pinned_version = PinnedVersion::new(
+            HummockVersion::from_rpc_protobuf(&PbHummockVersion {
+                id: 1,
+                max_committed_epoch: epoch0,
+                ..Default::default()
+            }),
+            unbounded_channel().0,
+        );
+
+        let (_version_update_tx, version_update_rx) = unbounded_channel();
+        let event_handler = HummockEventHandler::new_inner(
+            version_update_rx,
+            pinned_version,
+            None,
+            mock_sstable_store(),
+            Arc::new(HummockStateStoreMetrics::unused()),
+            &default_opts_for_test(),
+            Arc::new(|_, _| unreachable!("should not spawn upload task")),
+            Arc::new(|_, _, _, _| unreachable!("should not spawn merging task")),
+            CacheRefiller::default_spawn_refill_task(),
+        );
+
+        let tx = event_handler.event_sender();
+        let _join_handle = spawn(event_handler.start_hummock_event_handler_worker());
+
+        // `DrainPending` is acknowledged only after the previously enqueued events
+        // have been processed.
+        tx.send_data(HummockEvent::BufferMayFlush).await.unwrap();
+        tx.send_data(HummockEvent::BufferMayFlush).await.unwrap();
+        let (drain_tx, drain_rx) = oneshot::channel();
+        tx.send(HummockEvent::DrainPending(drain_tx)).unwrap();
+        drain_rx.await.unwrap();
+
+        // The event handler stays responsive afterwards.
+        let (drain_tx, drain_rx) = oneshot::channel();
+        tx.send(HummockEvent::DrainPending(drain_tx)).unwrap();
+        drain_rx.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_graceful_shutdown() {
+        let table_id = TableId::new(123);
+        let epoch0 = test_epoch(233);
+        let pinned_version = PinnedVersion::new(
+            HummockVersion::from_rpc_protobuf(&PbHummockVersion {
+                id: 1,
+                max_committed_epoch: epoch0,
+                ..Default::default()
+            }),
+            unbounded_channel().0,
+        );
+
+        let (_version_update_tx, version_update_rx) = unbounded_channel();
+
+        let (spawn_upload_task_tx, mut spawn_upload_task_rx) = unbounded_channel();
+        let event_handler = HummockEventHandler::new_inner(
+            version_update_rx,
+            pinned_version,
+            None,
+            mock_sstable_store(),
+            Arc::new(HummockStateStoreMetrics::unused()),
+            &default_opts_for_test(),
+            Arc::new(move |_, _| {
+                let (tx, rx) = oneshot::channel::<()>();
+                spawn_upload_task_tx.send(tx).unwrap();
+                spawn(async move {
+                    // wait for main thread to notify completion
+                    rx.await.unwrap();
+                    Ok(vec![])
+                })
+            }),
+            Arc::new(|_, _, _, _| unreachable!("should not spawn merging task")),
+            CacheRefiller::default_spawn_refill_task(),
+        );
+
+        let tx = event_handler.event_sender();
+        let join_handle = spawn(event_handler.start_hummock_event_handler_worker());
+
+        let (read_version_tx, read_version_rx) = oneshot::channel();
+        tx.send(HummockEvent::RegisterReadVersion {
+            table_id,
+            new_read_version_sender: read_version_tx,
+            is_replicated: false,
+            vnodes: Arc::new(Bitmap::ones(VirtualNode::COUNT)),
+        })
+        .unwrap();
+        let (read_version, guard) = read_version_rx.await.unwrap();
+        let instance_id = guard.instance_id;
+
+        let epoch1 = epoch0.next_epoch();
+        let imm = SharedBufferBatch::build_shared_buffer_batch(
+            epoch1,
+            0,
+            vec![(TableKey(Bytes::from("key")), HummockValue::Delete)],
+            10,
+            table_id,
+            instance_id,
+            None,
+        );
+        read_version
+            .write()
+            .update(VersionUpdate::Staging(StagingData::ImmMem(imm.clone())));
+        tx.send_data(HummockEvent::ImmToUploader {
+            imm,
+            flush_priority: None,
+        })
+        .await
+        .unwrap();
+        // ensure the imm has been taken by the uploader before sealing the epoch
+        let (flush_tx, flush_rx) = oneshot::channel();
+        tx.send(HummockEvent::FlushEvent(flush_tx)).unwrap();
+        flush_rx.await.unwrap();
+        tx.send(HummockEvent::SealEpoch {
+            epoch: epoch1,
+            is_checkpoint: true,
+        })
+        .unwrap();
+        let (sync_tx, sync_rx) = oneshot::channel();
+        tx.send(HummockEvent::AwaitSyncEpoch {
+            new_sync_epoch: epoch1,
+            sync_result_sender: sync_tx,
+        })
+        .unwrap();
+
+        let upload_finish_tx = spawn_upload_task_rx.recv().await.unwrap();
+
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
+        tx.send(HummockEvent::Shutdown(Some(shutdown_tx))).unwrap();
+
+        // the pending sync request is cancelled instead of left hanging
+        assert!(sync_rx.await.unwrap().is_err());
+
+        // shutdown is not acknowledged until the in-flight upload finishes
+        assert!(poll_fn(|cx| Poll::Ready(shutdown_rx.poll_unpin(cx)))
+            .await
+            .is_pending());
+
+        upload_finish_tx.send(()).unwrap();
+        shutdown_rx.await.unwrap();
+        join_handle.await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_clear_shared_buffer() {
         let epoch0 = 233;
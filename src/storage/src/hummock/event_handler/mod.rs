@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use parking_lot::{RwLock, RwLockReadGuard};
@@ -64,9 +64,25 @@ pub enum HummockEvent {
     /// Clear shared buffer and reset all states
     Clear(oneshot::Sender<()>, u64),
 
-    Shutdown,
+    /// Clear shared buffer batches, read versions and local instances of the given
+    /// tables only, leaving other tables untouched.
+    ClearTables {
+        table_ids: HashSet<TableId>,
+        notifier: oneshot::Sender<()>,
+    },
 
-    ImmToUploader(ImmutableMemtable),
+    /// Shutdown the event handler. When a notifier is given, it is acknowledged after
+    /// in-flight upload tasks have drained (or a deadline elapses).
+    Shutdown(Option<oneshot::Sender<()>>),
+
+    ImmToUploader {
+        imm: ImmutableMemtable,
+        /// An optional hint for ordering spills under memory pressure, typically the
+        /// size of `imm`: when the uploader has to flush to reclaim memory, data with
+        /// a higher accumulated priority is spilled first to free memory fastest.
+        /// Without a hint, spilling keeps the older-epoch-first order.
+        flush_priority: Option<u64>,
+    },
 
     SealEpoch {
         epoch: HummockEpoch,
@@ -80,6 +96,12 @@ pub enum HummockEvent {
         opts: SealCurrentEpochOptions,
     },
 
+    /// Drain the events that were enqueued before this one, then notify. Unlike the
+    /// test-only `FlushEvent`, the drain of the data channel is bounded by the backlog
+    /// length at the time the event is handled, so the acknowledgement is not starved
+    /// by a continuous influx of new data events.
+    DrainPending(oneshot::Sender<()>),
+
     #[cfg(any(test, feature = "test"))]
     /// Flush all previous event. When all previous events has been consumed, the event handler
     /// will notify
@@ -99,6 +121,35 @@ pub enum HummockEvent {
 }
 
 impl HummockEvent {
+    /// The variant name of the event, i.e. the category part of [`Self::to_debug_string`].
+    /// Used as a low-cardinality metric label.
+    pub(crate) fn category(&self) -> &'static str {
+        match self {
+            HummockEvent::BufferMayFlush => "BufferMayFlush",
+            HummockEvent::AwaitSyncEpoch { .. } => "AwaitSyncEpoch",
+            HummockEvent::Clear(_, _) => "Clear",
+            HummockEvent::ClearTables { .. } => "ClearTables",
+            HummockEvent::Shutdown(_) => "Shutdown",
+            HummockEvent::ImmToUploader { .. } => "ImmToUploader",
+            HummockEvent::SealEpoch { .. } => "SealEpoch",
+            HummockEvent::LocalSealEpoch { .. } => "LocalSealEpoch",
+            HummockEvent::DrainPending(_) => "DrainPending",
+            HummockEvent::RegisterReadVersion { .. } => "RegisterReadVersion",
+            HummockEvent::DestroyReadVersion { .. } => "DestroyReadVersion",
+            #[cfg(any(test, feature = "test"))]
+            HummockEvent::FlushEvent(_) => "FlushEvent",
+        }
+    }
+
+    /// Whether the event is a high-volume data event that is sent on the bounded data
+    /// channel, as opposed to a control event sent on the unbounded control channel.
+    fn is_data_event(&self) -> bool {
+        matches!(
+            self,
+            HummockEvent::BufferMayFlush | HummockEvent::ImmToUploader { .. }
+        )
+    }
+
     fn to_debug_string(&self) -> String {
         match self {
             HummockEvent::BufferMayFlush => "BufferMayFlush".to_string(),
@@ -110,9 +161,14 @@ impl HummockEvent {
 
             HummockEvent::Clear(_, prev_epoch) => format!("Clear {:?}", prev_epoch),
 
-            HummockEvent::Shutdown => "Shutdown".to_string(),
+            HummockEvent::ClearTables {
+                table_ids,
+                notifier: _,
+            } => format!("ClearTables {:?}", table_ids),
 
-            HummockEvent::ImmToUploader(imm) => {
+            HummockEvent::Shutdown(_) => "Shutdown".to_string(),
+
+            HummockEvent::ImmToUploader { imm, .. } => {
                 format!("ImmToUploader {:?}", imm)
             }
 
@@ -154,6 +210,8 @@ impl HummockEvent {
                 table_id, instance_id
             ),
 
+            HummockEvent::DrainPending(_) => "DrainPending".to_string(),
+
             #[cfg(any(test, feature = "test"))]
             HummockEvent::FlushEvent(_) => "FlushEvent".to_string(),
         }
@@ -168,6 +226,66 @@ impl std::fmt::Debug for HummockEvent {
     }
 }
 
+/// Sender of [`HummockEvent`]s to the event handler worker.
+///
+/// High-volume data events (see [`HummockEvent::is_data_event`]) go through a bounded
+/// channel so that writers are backpressured when the event handler falls behind, while
+/// control events go through a separate unbounded channel and are therefore never
+/// starved by a full data channel. The data channel capacity is configured by
+/// `hummock_event_channel_capacity`.
+pub struct HummockEventSender {
+    control_tx: mpsc::UnboundedSender<HummockEvent>,
+    data_tx: mpsc::Sender<HummockEvent>,
+}
+
+impl Clone for HummockEventSender {
+    fn clone(&self) -> Self {
+        Self {
+            control_tx: self.control_tx.clone(),
+            data_tx: self.data_tx.clone(),
+        }
+    }
+}
+
+impl HummockEventSender {
+    pub(crate) fn new(
+        control_tx: mpsc::UnboundedSender<HummockEvent>,
+        data_tx: mpsc::Sender<HummockEvent>,
+    ) -> Self {
+        Self {
+            control_tx,
+            data_tx,
+        }
+    }
+
+    /// Send a control event. Never blocks.
+    pub fn send(
+        &self,
+        event: HummockEvent,
+    ) -> Result<(), mpsc::error::SendError<HummockEvent>> {
+        debug_assert!(
+            !event.is_data_event(),
+            "data event {:?} should be sent with `send_data`",
+            event
+        );
+        self.control_tx.send(event)
+    }
+
+    /// Send a data event, waiting for channel capacity when the event handler is
+    /// congested.
+    pub async fn send_data(
+        &self,
+        event: HummockEvent,
+    ) -> Result<(), mpsc::error::SendError<HummockEvent>> {
+        debug_assert!(
+            event.is_data_event(),
+            "control event {:?} should be sent with `send`",
+            event
+        );
+        self.data_tx.send(event).await
+    }
+}
+
 pub type LocalInstanceId = u64;
 pub type HummockReadVersionRef = Arc<RwLock<HummockReadVersion>>;
 pub type ReadVersionMappingType = HashMap<TableId, HashMap<LocalInstanceId, HummockReadVersionRef>>;
@@ -191,6 +309,21 @@ impl<T> ReadOnlyRwLockRef<T> {
     }
 }
 
+impl ReadOnlyRwLockRef<ReadVersionMappingType> {
+    /// Number of read version instances registered for the table. Holds the read lock
+    /// only for the lookup, without cloning the mapping.
+    pub fn instance_count(&self, table_id: TableId) -> usize {
+        self.read().get(&table_id).map_or(0, HashMap::len)
+    }
+
+    /// Whether the given read version instance is registered for the table.
+    pub fn contains(&self, table_id: TableId, instance_id: LocalInstanceId) -> bool {
+        self.read()
+            .get(&table_id)
+            .map_or(false, |instances| instances.contains_key(&instance_id))
+    }
+}
+
 pub struct LocalInstanceGuard {
     pub table_id: TableId,
     pub instance_id: LocalInstanceId,
@@ -12,8 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::cmp::Reverse;
 use std::collections::hash_map::Entry;
-use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::fmt::{Debug, Display, Formatter};
 use std::future::{poll_fn, Future};
 use std::mem::swap;
@@ -353,6 +354,10 @@ struct UnsealedEpochData {
     // newer data at the front
     imms: VecDeque<ImmutableMemtable>,
     spilled_data: SpilledData,
+    /// The accumulated flush-priority hints of the buffered imms. Under memory
+    /// pressure, epochs with a higher priority are spilled first; with no hints the
+    /// priority stays zero for all epochs and spilling keeps the older-first order.
+    flush_priority: u64,
 
     table_watermarks: HashMap<TableId, (WatermarkDirection, Vec<VnodeWatermark>, BitmapBuilder)>,
 }
@@ -360,6 +365,7 @@ struct UnsealedEpochData {
 impl UnsealedEpochData {
     fn flush(&mut self, context: &UploaderContext) {
         let imms = self.imms.drain(..).collect_vec();
+        self.flush_priority = 0;
         if !imms.is_empty() {
             let task = UploadingTask::new(imms, context);
             context.stats.spill_task_counts_from_unsealed.inc();
@@ -451,6 +457,25 @@ impl SealedData {
         self.table_watermarks.clear();
     }
 
+    /// Drop the sealed imms and merging tasks of the given tables, leaving data of
+    /// other tables untouched.
+    fn clear_tables(&mut self, table_ids: &HashSet<TableId>) {
+        self.merging_tasks.retain(|task| {
+            if table_ids.contains(&task.table_id) {
+                task.join_handle.abort();
+                false
+            } else {
+                true
+            }
+        });
+        self.merged_imms
+            .retain(|imm| !table_ids.contains(&imm.table_id));
+        self.imms_by_table_shard
+            .retain(|(table_id, _), _| !table_ids.contains(table_id));
+        self.table_watermarks
+            .retain(|table_id, _| !table_ids.contains(table_id));
+    }
+
     /// Add the data of a newly sealed epoch.
     ///
     /// Note: it may happen that, for example, currently we hold `imms` and `spilled_data` of epoch
@@ -783,12 +808,50 @@ impl HummockUploader {
         &self.context.pinned_version
     }
 
+    /// Whether there is any spawned upload task that has not finished yet, either a
+    /// spilling task of unsealed or sealed data, or an uploading task of syncing data.
+    pub(crate) fn has_inflight_upload_tasks(&self) -> bool {
+        self.syncing_data
+            .iter()
+            .any(|syncing_data| syncing_data.uploading_tasks.is_some())
+            || !self.sealed_data.spilled_data.uploading_tasks.is_empty()
+            || self
+                .unsealed_data
+                .values()
+                .any(|unsealed_data| !unsealed_data.spilled_data.uploading_tasks.is_empty())
+    }
+
+    /// A human-readable summary of the data that has not been synced yet, for diagnosing
+    /// stuck sync requests.
+    pub(crate) fn describe_outstanding_uploads(&self) -> String {
+        let syncing = self
+            .syncing_data
+            .iter()
+            .map(|syncing_data| {
+                format!(
+                    "epoch {} ({} uploaded ssts, uploading tasks {}pending)",
+                    syncing_data.sync_epoch,
+                    syncing_data.uploaded.len(),
+                    if syncing_data.uploading_tasks.is_some() {
+                        ""
+                    } else {
+                        "not "
+                    },
+                )
+            })
+            .join(", ");
+        format!(
+            "max_sealed_epoch: {}, max_syncing_epoch: {}, max_synced_epoch: {}, syncing: [{}]",
+            self.max_sealed_epoch, self.max_syncing_epoch, self.max_synced_epoch, syncing
+        )
+    }
+
     pub(crate) fn get_synced_data(&self, epoch: HummockEpoch) -> Option<&SyncedDataState> {
         assert!(self.max_committed_epoch() < epoch && epoch <= self.max_synced_epoch);
         self.synced_data.get(&epoch)
     }
 
-    pub(crate) fn add_imm(&mut self, imm: ImmutableMemtable) {
+    pub(crate) fn add_imm(&mut self, imm: ImmutableMemtable, flush_priority: Option<u64>) {
         let epoch = imm.min_epoch();
         assert!(
             epoch > self.max_sealed_epoch,
@@ -796,11 +859,9 @@ impl HummockUploader {
             epoch,
             self.max_sealed_epoch
         );
-        self.unsealed_data
-            .entry(epoch)
-            .or_default()
-            .imms
-            .push_front(imm);
+        let unsealed_data = self.unsealed_data.entry(epoch).or_default();
+        unsealed_data.flush_priority += flush_priority.unwrap_or(0);
+        unsealed_data.imms.push_front(imm);
     }
 
     pub(crate) fn add_table_watermarks(
@@ -1018,9 +1079,21 @@ impl HummockUploader {
         }
 
         if self.context.buffer_tracker.need_more_flush() {
-            // iterate from older epoch to newer epoch
-            for unsealed_data in self.unsealed_data.values_mut() {
-                unsealed_data.flush(&self.context);
+            // Spill the epochs with the highest accumulated flush priority (typically
+            // the largest buffered size) first to free memory fastest. Epochs with
+            // equal priority — in particular all of them, when no hints were supplied —
+            // are flushed from older epoch to newer epoch.
+            let mut order: Vec<_> = self
+                .unsealed_data
+                .iter()
+                .map(|(epoch, unsealed_data)| (*epoch, unsealed_data.flush_priority))
+                .collect();
+            order.sort_by_key(|(epoch, flush_priority)| (Reverse(*flush_priority), *epoch));
+            for (epoch, _) in order {
+                self.unsealed_data
+                    .get_mut(&epoch)
+                    .expect("should exist")
+                    .flush(&self.context);
                 if !self.context.buffer_tracker.need_more_flush() {
                     break;
                 }
@@ -1040,6 +1113,22 @@ impl HummockUploader {
 
         // TODO: call `abort` on the uploading task join handle
     }
+
+    /// Clear the buffered data of the given tables only, leaving data of other tables
+    /// untouched. Data that has already been grouped into an upload task may span
+    /// multiple tables and cannot be dissected, so only data that has not started
+    /// uploading is dropped.
+    pub(crate) fn clear_tables(&mut self, table_ids: &HashSet<TableId>) {
+        for unsealed_data in self.unsealed_data.values_mut() {
+            unsealed_data
+                .imms
+                .retain(|imm| !table_ids.contains(&imm.table_id));
+            unsealed_data
+                .table_watermarks
+                .retain(|table_id, _| !table_ids.contains(table_id));
+        }
+        self.sealed_data.clear_tables(table_ids);
+    }
 }
 
 impl HummockUploader {
@@ -1405,7 +1494,7 @@ mod tests {
         let mut uploader = test_uploader(dummy_success_upload_future);
         let epoch1 = INITIAL_EPOCH.next_epoch();
         let imm = gen_imm(epoch1).await;
-        uploader.add_imm(imm.clone());
+        uploader.add_imm(imm.clone(), None);
         assert_eq!(1, uploader.unsealed_data.len());
         assert_eq!(
             epoch1 as HummockEpoch,
@@ -1488,8 +1577,8 @@ mod tests {
             imm1.instance_id = 1 as LocalInstanceId;
             imm2.instance_id = 2 as LocalInstanceId;
 
-            uploader.add_imm(imm1.clone());
-            uploader.add_imm(imm2.clone());
+            uploader.add_imm(imm1.clone(), None);
+            uploader.add_imm(imm2.clone(), None);
 
             // newer imm comes in front
             all_imms.push_front(imm1);
@@ -1573,7 +1662,7 @@ mod tests {
         let epoch2 = epoch1.next_epoch();
         let imm = gen_imm(epoch2).await;
         // epoch1 is empty while epoch2 is not. Going to seal empty epoch1.
-        uploader.add_imm(imm);
+        uploader.add_imm(imm, None);
         uploader.seal_epoch(epoch1);
         assert_eq!(epoch1, uploader.max_sealed_epoch);
 
@@ -1713,7 +1802,7 @@ mod tests {
         assert_eq!(epoch1, uploader.max_syncing_epoch);
         assert_eq!(epoch1, uploader.max_sealed_epoch);
 
-        uploader.add_imm(gen_imm(epoch6).await);
+        uploader.add_imm(gen_imm(epoch6).await, None);
         uploader.update_pinned_version(version2);
         assert_eq!(epoch2, uploader.max_synced_epoch);
         assert_eq!(epoch2, uploader.max_syncing_epoch);
@@ -1815,6 +1904,45 @@ mod tests {
         )
     }
 
+    #[tokio::test]
+    async fn test_uploader_flush_priority_order() {
+        let (buffer_tracker, mut uploader, new_task_notifier) = prepare_uploader_order_test();
+
+        let epoch1 = INITIAL_EPOCH.next_epoch();
+        let epoch2 = epoch1.next_epoch();
+        let memory_limiter = buffer_tracker.get_memory_limiter().clone();
+        let memory_limiter = Some(memory_limiter.deref());
+
+        let imm1 = gen_imm_with_limiter(epoch1, memory_limiter).await;
+        uploader.add_imm(imm1.clone(), Some(imm1.size() as u64));
+        let imm2 = gen_imm_with_limiter(epoch2, memory_limiter).await;
+        // With a higher flush priority, the newer epoch is spilled before the older
+        // one under memory pressure, overriding the default older-first order.
+        uploader.add_imm(imm2.clone(), Some(imm1.size() as u64 + 1));
+
+        let (await_start2, finish_tx2) = new_task_notifier(vec![imm2.batch_id()]);
+        let (await_start1, finish_tx1) = new_task_notifier(vec![imm1.batch_id()]);
+        uploader.may_flush();
+        await_start2.await;
+        await_start1.await;
+
+        // Spill completions are still consumed in epoch order.
+        finish_tx1.send(()).unwrap();
+        finish_tx2.send(()).unwrap();
+        if let UploaderEvent::DataSpilled(sst) = uploader.next_event().await {
+            assert_eq!(&vec![imm1.batch_id()], sst.imm_ids());
+            assert_eq!(&vec![epoch1], sst.epochs());
+        } else {
+            unreachable!("should be data spilled");
+        }
+        if let UploaderEvent::DataSpilled(sst) = uploader.next_event().await {
+            assert_eq!(&vec![imm2.batch_id()], sst.imm_ids());
+            assert_eq!(&vec![epoch2], sst.epochs());
+        } else {
+            unreachable!("should be data spilled");
+        }
+    }
+
     #[tokio::test]
     async fn test_uploader_finish_in_order() {
         let (buffer_tracker, mut uploader, new_task_notifier) = prepare_uploader_order_test();
@@ -1826,11 +1954,11 @@ mod tests {
 
         // imm2 contains data in newer epoch, but added first
         let imm2 = gen_imm_with_limiter(epoch2, memory_limiter).await;
-        uploader.add_imm(imm2.clone());
+        uploader.add_imm(imm2.clone(), None);
         let imm1_1 = gen_imm_with_limiter(epoch1, memory_limiter).await;
-        uploader.add_imm(imm1_1.clone());
+        uploader.add_imm(imm1_1.clone(), None);
         let imm1_2 = gen_imm_with_limiter(epoch1, memory_limiter).await;
-        uploader.add_imm(imm1_2.clone());
+        uploader.add_imm(imm1_2.clone(), None);
 
         // imm1 will be spilled first
         let (await_start1, finish_tx1) =
@@ -1861,12 +1989,12 @@ mod tests {
         }
 
         let imm1_3 = gen_imm_with_limiter(epoch1, memory_limiter).await;
-        uploader.add_imm(imm1_3.clone());
+        uploader.add_imm(imm1_3.clone(), None);
         let (await_start1_3, finish_tx1_3) = new_task_notifier(vec![imm1_3.batch_id()]);
         uploader.may_flush();
         await_start1_3.await;
         let imm1_4 = gen_imm_with_limiter(epoch1, memory_limiter).await;
-        uploader.add_imm(imm1_4.clone());
+        uploader.add_imm(imm1_4.clone(), None);
         let (await_start1_4, finish_tx1_4) = new_task_notifier(vec![imm1_4.batch_id()]);
         uploader.seal_epoch(epoch1);
         uploader.start_sync_epoch(epoch1);
@@ -1881,17 +2009,17 @@ mod tests {
 
         let epoch3 = epoch2.next_epoch();
         let imm3_1 = gen_imm_with_limiter(epoch3, memory_limiter).await;
-        uploader.add_imm(imm3_1.clone());
+        uploader.add_imm(imm3_1.clone(), None);
         let (await_start3_1, finish_tx3_1) = new_task_notifier(vec![imm3_1.batch_id()]);
         uploader.may_flush();
         await_start3_1.await;
         let imm3_2 = gen_imm_with_limiter(epoch3, memory_limiter).await;
-        uploader.add_imm(imm3_2.clone());
+        uploader.add_imm(imm3_2.clone(), None);
         let (await_start3_2, finish_tx3_2) = new_task_notifier(vec![imm3_2.batch_id()]);
         uploader.may_flush();
         await_start3_2.await;
         let imm3_3 = gen_imm_with_limiter(epoch3, memory_limiter).await;
-        uploader.add_imm(imm3_3.clone());
+        uploader.add_imm(imm3_3.clone(), None);
 
         // current uploader state:
         // unsealed: epoch3: imm: imm3_3, uploading: [imm3_2], [imm3_1]
@@ -1900,7 +2028,7 @@ mod tests {
 
         let epoch4 = epoch3.next_epoch();
         let imm4 = gen_imm_with_limiter(epoch4, memory_limiter).await;
-        uploader.add_imm(imm4.clone());
+        uploader.add_imm(imm4.clone(), None);
         assert_uploader_pending(&mut uploader).await;
 
         // current uploader state:
@@ -0,0 +1,1016 @@
+// Copyright 2024 RisingWave Labs
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Bound::*;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use risingwave_common::array::{Op, StreamChunk};
+use risingwave_common::buffer::Bitmap;
+use risingwave_common::row::OwnedRow;
+use risingwave_common::types::DataType;
+use risingwave_common::util::chunk_coalesce::DataChunkBuilder;
+use risingwave_common::util::epoch::{Epoch, MAX_SPILL_TIMES};
+use risingwave_common::util::value_encoding::ValueRowDeserializer;
+use risingwave_hummock_sdk::change_log::{ChangeLogShard, EpochNewChangeLog};
+use risingwave_hummock_sdk::key::{FullKey, TableKey, TableKeyRange, UserKey};
+use risingwave_hummock_sdk::{EpochWithGap, HummockEpoch};
+
+use crate::hummock::iterator::{Forward, HummockIterator};
+use crate::hummock::value::HummockValue;
+use crate::hummock::{HummockError, HummockResult};
+
+/// A single decoded change record of a user key, yielded by [`ChangeLogIter`].
+///
+/// The variant is derived from the presence of the key in the old-value and new-value
+/// streams of the change log: a key only present in the new-value stream is an
+/// `Insert`, a key only present in the old-value stream is a `Delete`, and a key
+/// present in both is an `Update`.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ChangeLogValue<T> {
+    Insert(T),
+    Delete(T),
+    Update { old_value: T, new_value: T },
+}
+
+/// Read mode of [`ChangeLogIter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChangeLogReadMode {
+    /// Yield every change of a user key within the epoch range.
+    #[default]
+    All,
+    /// Collapse multiple changes of the same user key within the range into a single
+    /// net change reflecting only the boundary states, e.g. for subscribers that only
+    /// care about the final state of each key.
+    LatestOnly,
+}
+
+impl<T> ChangeLogValue<T> {
+    /// Decodes a change record from the old and new value of a key within the epoch
+    /// range. Returns `None` when the key appears in neither stream, i.e. it did not
+    /// change within the range.
+    pub fn from_parts(old_value: Option<T>, new_value: Option<T>) -> Option<Self> {
+        match (old_value, new_value) {
+            (None, Some(new_value)) => Some(Self::Insert(new_value)),
+            (Some(old_value), None) => Some(Self::Delete(old_value)),
+            (Some(old_value), Some(new_value)) => Some(Self::Update {
+                old_value,
+                new_value,
+            }),
+            (None, None) => None,
+        }
+    }
+
+    /// Decomposes the change record into its old and new value.
+    pub fn into_parts(self) -> (Option<T>, Option<T>) {
+        match self {
+            Self::Insert(new_value) => (None, Some(new_value)),
+            Self::Delete(old_value) => (Some(old_value), None),
+            Self::Update {
+                old_value,
+                new_value,
+            } => (Some(old_value), Some(new_value)),
+        }
+    }
+
+    /// Collapses a sequence of changes of the same user key, ordered by epoch, into a
+    /// single net change from the pre-range state to the post-range state. Returns
+    /// `None` when the key ends up unchanged, e.g. it was inserted and deleted within
+    /// the range.
+    pub fn collapse(changes: impl IntoIterator<Item = Self>) -> Option<Self> {
+        let mut iter = changes.into_iter();
+        let (old_value, mut new_value) = iter.next()?.into_parts();
+        for change in iter {
+            new_value = change.into_parts().1;
+        }
+        Self::from_parts(old_value, new_value)
+    }
+
+    pub fn map<O>(self, mut f: impl FnMut(T) -> O) -> ChangeLogValue<O> {
+        match self {
+            Self::Insert(value) => ChangeLogValue::Insert(f(value)),
+            Self::Delete(value) => ChangeLogValue::Delete(f(value)),
+            Self::Update {
+                old_value,
+                new_value,
+            } => ChangeLogValue::Update {
+                old_value: f(old_value),
+                new_value: f(new_value),
+            },
+        }
+    }
+}
+
+/// A change record yielded to change-log subscribers: the decoded [`ChangeLogValue`]
+/// together with the epoch it was committed in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeLogRecord<T> {
+    pub value: ChangeLogValue<T>,
+    pub epoch: HummockEpoch,
+    /// Commit time of `epoch` in Unix milliseconds, decoded from the physical part of
+    /// the epoch. `None` when the subscriber did not request commit timestamps.
+    pub commit_ts_millis: Option<u64>,
+}
+
+impl<T> ChangeLogRecord<T> {
+    pub fn new(value: ChangeLogValue<T>, epoch: HummockEpoch, with_commit_ts: bool) -> Self {
+        Self {
+            value,
+            epoch,
+            commit_ts_millis: with_commit_ts.then(|| Epoch(epoch).as_unix_millis()),
+        }
+    }
+}
+
+/// Iterator over the change log of a table within an epoch range.
+///
+/// It merges a `new_value_iter` over the new-value SSTs and an `old_value_iter` over the
+/// old-value SSTs of the table's change log, aligned by user key, to yield per-key change
+/// records within `[min_epoch, max_epoch]`.
+pub struct ChangeLogIter<NI: HummockIterator<Direction = Forward>, OI: HummockIterator<Direction = Forward>>
+{
+    new_value_iter: NI,
+    old_value_iter: OI,
+    max_epoch: HummockEpoch,
+    min_epoch: HummockEpoch,
+    key_range: TableKeyRange,
+    /// When set, only change records of vnodes in the filter are yielded. Shards whose
+    /// `vnode_bitmap` does not intersect the filter are skipped entirely, so a reader
+    /// that owns only a subset of vnodes does not fetch SSTs it would discard.
+    vnode_filter: Option<Arc<Bitmap>>,
+    /// Whether the yielded [`ChangeLogRecord`]s carry the commit timestamp decoded
+    /// from their epoch.
+    with_commit_ts: bool,
+    /// Whether all changes or only the net change of each user key is yielded.
+    read_mode: ChangeLogReadMode,
+    /// When aligning the old-value iterator to a new-value entry, step over at most
+    /// this many entries before falling back to a direct `seek`, so that skewed data
+    /// (e.g. many keys present in only one stream) does not make the alignment scan
+    /// unbounded.
+    max_buffered_entries: usize,
+    /// The change record currently pointed to, `None` when the iterator is invalid.
+    current: Option<(UserKey<Bytes>, ChangeLogRecord<Bytes>)>,
+}
+
+impl<NI: HummockIterator<Direction = Forward>, OI: HummockIterator<Direction = Forward>>
+    ChangeLogIter<NI, OI>
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        new_value_iter: NI,
+        old_value_iter: OI,
+        key_range: TableKeyRange,
+        max_epoch: HummockEpoch,
+        min_epoch: HummockEpoch,
+        vnode_filter: Option<Arc<Bitmap>>,
+        with_commit_ts: bool,
+        read_mode: ChangeLogReadMode,
+        max_buffered_entries: usize,
+    ) -> Self {
+        Self {
+            new_value_iter,
+            old_value_iter,
+            max_epoch,
+            min_epoch,
+            key_range,
+            vnode_filter,
+            with_commit_ts,
+            read_mode,
+            max_buffered_entries,
+            current: None,
+        }
+    }
+
+    /// Moves a valid iterator to the next change record.
+    pub async fn next(&mut self) -> HummockResult<()> {
+        assert!(self.is_valid());
+        self.current = None;
+        self.try_advance_to_next_valid().await
+    }
+
+    /// Resets the iterating position to the first change record within the key range.
+    pub async fn rewind(&mut self) -> HummockResult<()> {
+        self.current = None;
+        self.new_value_iter.rewind().await?;
+        self.old_value_iter.rewind().await?;
+        self.try_advance_to_next_valid().await
+    }
+
+    /// Resets the iterating position to the first change record whose user key is
+    /// `>= user_key`, clamped to the key range.
+    pub async fn seek(&mut self, user_key: UserKey<&[u8]>) -> HummockResult<()> {
+        self.current = None;
+        let table_key = match &self.key_range.0 {
+            Included(begin_key) if begin_key.as_ref() > user_key.table_key.as_ref() => {
+                TableKey(begin_key.as_ref())
+            }
+            Excluded(_) => unimplemented!("excluded begin key is not supported"),
+            _ => user_key.table_key,
+        };
+        let full_key = FullKey {
+            user_key: UserKey::new(user_key.table_id, table_key),
+            epoch_with_gap: EpochWithGap::new(self.max_epoch, MAX_SPILL_TIMES),
+        };
+        self.new_value_iter.seek(full_key).await?;
+        self.old_value_iter.seek(full_key).await?;
+        self.try_advance_to_next_valid().await
+    }
+
+    /// Indicates whether the iterator is pointing to a valid change record.
+    pub fn is_valid(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// The user key of the current change record.
+    ///
+    /// Note: before calling the function you need to ensure that the iterator is valid.
+    pub fn key(&self) -> UserKey<&[u8]> {
+        self.current.as_ref().expect("should be valid").0.as_ref()
+    }
+
+    /// The current change record, carrying the old and/or new value of the key together
+    /// with the epoch it was committed in.
+    ///
+    /// Note: before calling the function you need to ensure that the iterator is valid.
+    pub fn log_record(&self) -> &ChangeLogRecord<Bytes> {
+        &self.current.as_ref().expect("should be valid").1
+    }
+
+    /// Advances to the next entry of `new_value_iter` that is within the epoch range,
+    /// within the key range and matched by the vnode filter, decodes its change record
+    /// and stores it in `current`. Entries whose change turns out to be a no-op (e.g. a
+    /// key inserted and deleted within the range under [`ChangeLogReadMode::LatestOnly`])
+    /// are skipped.
+    async fn try_advance_to_next_valid(&mut self) -> HummockResult<()> {
+        loop {
+            if !self.new_value_iter.is_valid() {
+                return Ok(());
+            }
+
+            {
+                let full_key = self.new_value_iter.key();
+                let table_key = &full_key.user_key.table_key;
+
+                // Keys are yielded in ascending order, so once the end bound is passed no
+                // further entry can be in range.
+                let out_of_range = match &self.key_range.1 {
+                    Included(end_key) => table_key.as_ref() > end_key.as_ref(),
+                    Excluded(end_key) => table_key.as_ref() >= end_key.as_ref(),
+                    Unbounded => false,
+                };
+                if out_of_range {
+                    return Ok(());
+                }
+
+                let epoch = full_key.epoch_with_gap.pure_epoch();
+                let below_range = match &self.key_range.0 {
+                    Included(begin_key) => table_key.as_ref() < begin_key.as_ref(),
+                    Excluded(begin_key) => table_key.as_ref() <= begin_key.as_ref(),
+                    Unbounded => false,
+                };
+                let vnode_filtered_out = match &self.vnode_filter {
+                    Some(filter) => !filter.is_set(table_key.vnode_part().to_index()),
+                    None => false,
+                };
+                if epoch < self.min_epoch
+                    || epoch > self.max_epoch
+                    || below_range
+                    || vnode_filtered_out
+                {
+                    self.new_value_iter.next().await?;
+                    continue;
+                }
+            }
+
+            let (full_key, change) = self.current_change().await?;
+            let epoch = full_key.epoch_with_gap.pure_epoch();
+            self.new_value_iter.next().await?;
+
+            let change = match self.read_mode {
+                ChangeLogReadMode::All => change,
+                ChangeLogReadMode::LatestOnly => {
+                    // Fold the remaining in-range changes of the same user key into a
+                    // single net change. The inner iterators yield epochs from newest
+                    // to oldest, so the first present change carries the net new value
+                    // and every older one in turn overwrites the net old value, until
+                    // the oldest in-range change holds the pre-range state. This is
+                    // equivalent to collecting all changes and [`ChangeLogValue::collapse`]-ing
+                    // them, but keeps memory bounded for a key changed many times
+                    // within the range.
+                    let mut old_value = None;
+                    let mut new_value = None;
+                    let mut seen_change = false;
+                    let mut fold = |change: Option<ChangeLogValue<Bytes>>| {
+                        if let Some(change) = change {
+                            let (old, new) = change.into_parts();
+                            if !seen_change {
+                                new_value = new;
+                                seen_change = true;
+                            }
+                            old_value = old;
+                        }
+                    };
+                    fold(change);
+                    while self.new_value_iter.is_valid()
+                        && self.new_value_iter.key().user_key == full_key.user_key.as_ref()
+                    {
+                        let epoch = self.new_value_iter.key().epoch_with_gap.pure_epoch();
+                        if epoch >= self.min_epoch && epoch <= self.max_epoch {
+                            let (_, change) = self.current_change().await?;
+                            fold(change);
+                        }
+                        self.new_value_iter.next().await?;
+                    }
+                    ChangeLogValue::from_parts(old_value, new_value)
+                }
+            };
+
+            if let Some(change) = change {
+                self.current = Some((
+                    full_key.user_key,
+                    ChangeLogRecord::new(change, epoch, self.with_commit_ts),
+                ));
+                return Ok(());
+            }
+        }
+    }
+
+    /// Decodes the change record of the entry currently pointed to by `new_value_iter`,
+    /// advancing `old_value_iter` to the aligned position. Returns `None` as the change
+    /// when the key has neither an old nor a new value at this epoch.
+    async fn current_change(
+        &mut self,
+    ) -> HummockResult<(FullKey<Bytes>, Option<ChangeLogValue<Bytes>>)> {
+        let full_key = self.new_value_iter.key().copy_into::<Bytes>();
+        let new_value = match self.new_value_iter.value() {
+            HummockValue::Put(value) => Some(Bytes::copy_from_slice(value)),
+            HummockValue::Delete => None,
+        };
+        let mut stepped = 0;
+        while self.old_value_iter.is_valid() && self.old_value_iter.key() < full_key.to_ref() {
+            if stepped >= self.max_buffered_entries {
+                // The old-value iterator is far behind; re-seek it directly to the
+                // target key instead of stepping over every intermediate entry.
+                self.old_value_iter.seek(full_key.to_ref()).await?;
+                break;
+            }
+            self.old_value_iter.next().await?;
+            stepped += 1;
+        }
+        let old_value = if self.old_value_iter.is_valid()
+            && self.old_value_iter.key() == full_key.to_ref()
+        {
+            match self.old_value_iter.value() {
+                HummockValue::Put(value) => Some(Bytes::copy_from_slice(value)),
+                HummockValue::Delete => None,
+            }
+        } else {
+            None
+        };
+        Ok((full_key, ChangeLogValue::from_parts(old_value, new_value)))
+    }
+}
+
+/// Returns whether a change log shard is relevant to a reader with the given vnode filter.
+/// A `None` filter matches all shards.
+pub fn shard_matches_vnode_filter(shard: &ChangeLogShard, vnode_filter: Option<&Bitmap>) -> bool {
+    match vnode_filter {
+        Some(filter) => (&*shard.vnode_bitmap & filter).count_ones() > 0,
+        None => true,
+    }
+}
+
+/// Returns the shards of `change_log` that a reader with the given vnode filter should read.
+pub fn filter_shards<'a>(
+    change_log: &'a EpochNewChangeLog,
+    vnode_filter: Option<&'a Bitmap>,
+) -> impl Iterator<Item = &'a ChangeLogShard> + 'a {
+    change_log
+        .shards
+        .iter()
+        .filter(move |shard| shard_matches_vnode_filter(shard, vnode_filter))
+}
+
+/// Reads the change log of a table as [`StreamChunk`]s ready for the stream layer to
+/// emit, e.g. to `SUBSCRIBE` consumers.
+///
+/// Each change record yielded by the underlying [`ChangeLogIter`] is decoded into rows
+/// with the given row deserializer and appended with the [`Op`] derived from the
+/// presence of the key in the old-value and new-value streams: an insert becomes an
+/// `Insert` row, a delete a `Delete` row, and an update an `UpdateDelete` row followed
+/// by an `UpdateInsert` row carrying the old and new value respectively. The two rows
+/// of an update pair are never split across chunks.
+pub struct ChangeLogReader<
+    NI: HummockIterator<Direction = Forward>,
+    OI: HummockIterator<Direction = Forward>,
+    D: ValueRowDeserializer,
+> {
+    iter: ChangeLogIter<NI, OI>,
+    row_deserializer: D,
+    chunk_size: usize,
+    ops: Vec<Op>,
+    builder: DataChunkBuilder,
+}
+
+impl<NI, OI, D> ChangeLogReader<NI, OI, D>
+where
+    NI: HummockIterator<Direction = Forward>,
+    OI: HummockIterator<Direction = Forward>,
+    D: ValueRowDeserializer,
+{
+    pub fn new(
+        iter: ChangeLogIter<NI, OI>,
+        data_types: Vec<DataType>,
+        chunk_size: usize,
+        row_deserializer: D,
+    ) -> Self {
+        assert!(chunk_size >= 2, "chunk size should fit an update pair");
+        Self {
+            iter,
+            row_deserializer,
+            chunk_size,
+            ops: Vec::with_capacity(chunk_size),
+            builder: DataChunkBuilder::new(data_types, chunk_size),
+        }
+    }
+
+    pub async fn init(&mut self) -> HummockResult<()> {
+        self.iter.rewind().await
+    }
+
+    /// Returns the next chunk of at most `chunk_size` change rows, or `None` when the
+    /// change log is exhausted.
+    pub async fn next_chunk(&mut self) -> HummockResult<Option<StreamChunk>> {
+        while self.iter.is_valid() {
+            // Flush early when appending an update pair would split it across chunks.
+            if matches!(self.iter.log_record().value, ChangeLogValue::Update { .. })
+                && self.builder.buffered_count() + 2 > self.chunk_size
+                && !self.builder.is_empty()
+            {
+                let chunk = self.builder.consume_all().expect("should be non-empty");
+                return Ok(Some(StreamChunk::from_parts(
+                    std::mem::take(&mut self.ops),
+                    chunk,
+                )));
+            }
+            let value = self.iter.log_record().value.clone();
+            let chunk = match &value {
+                ChangeLogValue::Insert(new_value) => self.append_row(Op::Insert, new_value)?,
+                ChangeLogValue::Delete(old_value) => self.append_row(Op::Delete, old_value)?,
+                ChangeLogValue::Update {
+                    old_value,
+                    new_value,
+                } => {
+                    let chunk = self.append_row(Op::UpdateDelete, old_value)?;
+                    debug_assert!(chunk.is_none(), "update pair should not be split");
+                    self.append_row(Op::UpdateInsert, new_value)?
+                }
+            };
+            self.iter.next().await?;
+            if let Some(chunk) = chunk {
+                return Ok(Some(chunk));
+            }
+        }
+        Ok(self
+            .builder
+            .consume_all()
+            .map(|chunk| StreamChunk::from_parts(std::mem::take(&mut self.ops), chunk)))
+    }
+
+    /// Decodes `value` into a row and appends it with `op`, returning a chunk when the
+    /// builder becomes full.
+    fn append_row(&mut self, op: Op, value: &[u8]) -> HummockResult<Option<StreamChunk>> {
+        let row = OwnedRow::new(
+            self.row_deserializer
+                .deserialize(value)
+                .map_err(HummockError::decode_error)?,
+        );
+        self.ops.push(op);
+        Ok(self
+            .builder
+            .append_one_row(row)
+            .map(|chunk| StreamChunk::from_parts(std::mem::take(&mut self.ops), chunk)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::buffer::BitmapBuilder;
+    use risingwave_common::catalog::TableId;
+    use risingwave_common::hash::VirtualNode;
+    use risingwave_common::row::RowDeserializer;
+    use risingwave_common::types::ScalarImpl;
+    use risingwave_common::util::epoch::test_epoch;
+    use risingwave_common::util::value_encoding::{BasicSerializer, ValueRowSerializer};
+
+    use super::*;
+    use crate::hummock::iterator::test_utils::transform_shared_buffer;
+    use crate::hummock::iterator::MergeIterator;
+    use crate::hummock::shared_buffer::shared_buffer_batch::SharedBufferBatch;
+
+    fn vnode_bitmap(vnodes: impl IntoIterator<Item = usize>) -> Arc<Bitmap> {
+        let mut builder = BitmapBuilder::zeroed(VirtualNode::COUNT);
+        for vnode in vnodes {
+            builder.set(vnode, true);
+        }
+        Arc::new(builder.finish())
+    }
+
+    fn shard(vnodes: impl IntoIterator<Item = usize>) -> ChangeLogShard {
+        ChangeLogShard {
+            vnode_bitmap: vnode_bitmap(vnodes),
+            new_value: vec![],
+            old_value: vec![],
+        }
+    }
+
+    #[test]
+    fn test_change_log_value_from_parts() {
+        // A key only present in the new-value stream is an insert.
+        assert_eq!(
+            ChangeLogValue::from_parts(None, Some("v1")),
+            Some(ChangeLogValue::Insert("v1"))
+        );
+        // A key only present in the old-value stream is a delete.
+        assert_eq!(
+            ChangeLogValue::from_parts(Some("v1"), None),
+            Some(ChangeLogValue::Delete("v1"))
+        );
+        // A key present in both streams is an update.
+        assert_eq!(
+            ChangeLogValue::from_parts(Some("v1"), Some("v2")),
+            Some(ChangeLogValue::Update {
+                old_value: "v1",
+                new_value: "v2",
+            })
+        );
+        // A key that did not change within the range yields no record.
+        assert_eq!(ChangeLogValue::<&str>::from_parts(None, None), None);
+    }
+
+    #[test]
+    fn test_change_log_value_collapse() {
+        // A key changed five times within the range collapses into a single net update
+        // from its pre-range value to its post-range value.
+        let changes = (0..5).map(|i| ChangeLogValue::Update {
+            old_value: i,
+            new_value: i + 1,
+        });
+        assert_eq!(
+            ChangeLogValue::collapse(changes),
+            Some(ChangeLogValue::Update {
+                old_value: 0,
+                new_value: 5,
+            })
+        );
+
+        // A key inserted and then updated within the range is a net insert.
+        let changes = [
+            ChangeLogValue::Insert("v1"),
+            ChangeLogValue::Update {
+                old_value: "v1",
+                new_value: "v2",
+            },
+        ];
+        assert_eq!(
+            ChangeLogValue::collapse(changes),
+            Some(ChangeLogValue::Insert("v2"))
+        );
+
+        // A key inserted and deleted within the range ends up unchanged.
+        let changes = [ChangeLogValue::Insert("v1"), ChangeLogValue::Delete("v1")];
+        assert_eq!(ChangeLogValue::collapse(changes), None);
+
+        // An empty sequence yields no record.
+        assert_eq!(ChangeLogValue::<&str>::collapse([]), None);
+    }
+
+    #[test]
+    fn test_change_log_record_commit_ts() {
+        let commit_millis = 1_700_000_000_000;
+        let epoch = Epoch::from_unix_millis(commit_millis);
+
+        // When requested, the commit timestamp is decoded from the physical part of the
+        // epoch.
+        let record = ChangeLogRecord::new(ChangeLogValue::Insert("v1"), epoch.0, true);
+        assert_eq!(record.commit_ts_millis, Some(commit_millis));
+        assert_eq!(record.commit_ts_millis, Some(epoch.as_unix_millis()));
+
+        // When not requested, no timestamp is carried.
+        let record = ChangeLogRecord::new(ChangeLogValue::Insert("v1"), epoch.0, false);
+        assert_eq!(record.commit_ts_millis, None);
+    }
+
+    async fn build_change_log_iter(
+        max_epoch: HummockEpoch,
+        min_epoch: HummockEpoch,
+        read_mode: ChangeLogReadMode,
+    ) -> ChangeLogIter<
+        MergeIterator<crate::hummock::shared_buffer::shared_buffer_batch::SharedBufferBatchIterator<Forward>>,
+        crate::hummock::shared_buffer::shared_buffer_batch::SharedBufferBatchIterator<Forward>,
+    > {
+        let table_id = TableId::new(1);
+        let (epoch1, epoch2) = (test_epoch(1), test_epoch(2));
+        // At epoch1, `a` and `b` are inserted. At epoch2, `a` is updated and `b` is
+        // deleted, so the old-value stream of epoch2 holds their epoch1 values.
+        let new1 = SharedBufferBatch::for_test(
+            transform_shared_buffer(vec![
+                (b"a".to_vec(), HummockValue::put(Bytes::from("a1"))),
+                (b"b".to_vec(), HummockValue::put(Bytes::from("b1"))),
+            ]),
+            epoch1,
+            table_id,
+        );
+        let new2 = SharedBufferBatch::for_test(
+            transform_shared_buffer(vec![
+                (b"a".to_vec(), HummockValue::put(Bytes::from("a2"))),
+                (b"b".to_vec(), HummockValue::delete()),
+            ]),
+            epoch2,
+            table_id,
+        );
+        let old2 = SharedBufferBatch::for_test(
+            transform_shared_buffer(vec![
+                (b"a".to_vec(), HummockValue::put(Bytes::from("a1"))),
+                (b"b".to_vec(), HummockValue::put(Bytes::from("b1"))),
+            ]),
+            epoch2,
+            table_id,
+        );
+        let new_value_iter =
+            MergeIterator::new(vec![new1.into_forward_iter(), new2.into_forward_iter()]);
+        let old_value_iter = old2.into_forward_iter();
+        ChangeLogIter::new(
+            new_value_iter,
+            old_value_iter,
+            (Unbounded, Unbounded),
+            max_epoch,
+            min_epoch,
+            None,
+            false,
+            read_mode,
+            1024,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_change_log_iter_all() {
+        let (epoch1, epoch2) = (test_epoch(1), test_epoch(2));
+        let mut iter = build_change_log_iter(epoch2, epoch1, ChangeLogReadMode::All).await;
+        iter.rewind().await.unwrap();
+
+        // Per user key, changes are yielded from the newest epoch to the oldest.
+        let expected = [
+            (
+                b"a".as_slice(),
+                ChangeLogValue::Update {
+                    old_value: Bytes::from("a1"),
+                    new_value: Bytes::from("a2"),
+                },
+                epoch2,
+            ),
+            (
+                b"a".as_slice(),
+                ChangeLogValue::Insert(Bytes::from("a1")),
+                epoch1,
+            ),
+            (
+                b"b".as_slice(),
+                ChangeLogValue::Delete(Bytes::from("b1")),
+                epoch2,
+            ),
+            (
+                b"b".as_slice(),
+                ChangeLogValue::Insert(Bytes::from("b1")),
+                epoch1,
+            ),
+        ];
+        for (key, value, epoch) in expected {
+            assert!(iter.is_valid());
+            assert_eq!(iter.key().table_key.as_ref(), key);
+            assert_eq!(iter.log_record().value, value);
+            assert_eq!(iter.log_record().epoch, epoch);
+            iter.next().await.unwrap();
+        }
+        assert!(!iter.is_valid());
+
+        // Restricting the epoch range to epoch2 only yields the epoch2 changes.
+        let mut iter = build_change_log_iter(epoch2, epoch2, ChangeLogReadMode::All).await;
+        iter.rewind().await.unwrap();
+        assert_eq!(iter.key().table_key.as_ref(), b"a".as_slice());
+        assert_eq!(iter.log_record().epoch, epoch2);
+        iter.next().await.unwrap();
+        assert_eq!(iter.key().table_key.as_ref(), b"b".as_slice());
+        assert_eq!(iter.log_record().epoch, epoch2);
+        iter.next().await.unwrap();
+        assert!(!iter.is_valid());
+    }
+
+    #[tokio::test]
+    async fn test_change_log_iter_skewed_alignment() {
+        let table_id = TableId::new(1);
+        let epoch = test_epoch(1);
+        // The new-value stream holds a single key at the very end of the key space,
+        // while the old-value stream holds many keys before it. With a small step cap,
+        // aligning the old-value iterator falls back to a direct seek instead of
+        // stepping over every intermediate entry, and must still find the old value.
+        let mut old_items: Vec<_> = (0..100)
+            .map(|i| {
+                (
+                    format!("key{:03}", i).into_bytes(),
+                    HummockValue::put(Bytes::from("old")),
+                )
+            })
+            .collect();
+        old_items.push((b"z".to_vec(), HummockValue::put(Bytes::from("z_old"))));
+        let old = SharedBufferBatch::for_test(transform_shared_buffer(old_items), epoch, table_id);
+        let new = SharedBufferBatch::for_test(
+            transform_shared_buffer(vec![(
+                b"z".to_vec(),
+                HummockValue::put(Bytes::from("z_new")),
+            )]),
+            epoch,
+            table_id,
+        );
+        let mut iter = ChangeLogIter::new(
+            MergeIterator::new(vec![new.into_forward_iter()]),
+            old.into_forward_iter(),
+            (Unbounded, Unbounded),
+            epoch,
+            epoch,
+            None,
+            false,
+            ChangeLogReadMode::All,
+            4,
+        );
+        iter.rewind().await.unwrap();
+
+        assert!(iter.is_valid());
+        assert_eq!(iter.key().table_key.as_ref(), b"z".as_slice());
+        assert_eq!(
+            iter.log_record().value,
+            ChangeLogValue::Update {
+                old_value: Bytes::from("z_old"),
+                new_value: Bytes::from("z_new"),
+            }
+        );
+        iter.next().await.unwrap();
+        assert!(!iter.is_valid());
+    }
+
+    #[tokio::test]
+    async fn test_change_log_iter_latest_only() {
+        let (epoch1, epoch2) = (test_epoch(1), test_epoch(2));
+        let mut iter = build_change_log_iter(epoch2, epoch1, ChangeLogReadMode::LatestOnly).await;
+        iter.rewind().await.unwrap();
+
+        // `a` was inserted and then updated within the range, collapsing into a net
+        // insert of its final value. `b` was inserted and deleted within the range, so
+        // it ends up unchanged and yields no record.
+        assert!(iter.is_valid());
+        assert_eq!(iter.key().table_key.as_ref(), b"a".as_slice());
+        assert_eq!(
+            iter.log_record().value,
+            ChangeLogValue::Insert(Bytes::from("a2"))
+        );
+        assert_eq!(iter.log_record().epoch, epoch2);
+        iter.next().await.unwrap();
+        assert!(!iter.is_valid());
+    }
+
+    #[tokio::test]
+    async fn test_change_log_iter_latest_only_many_updates() {
+        let table_id = TableId::new(1);
+        let epochs: Vec<_> = (1..=4).map(test_epoch).collect();
+        // `a` is inserted at epoch1 and updated at every following epoch, so the
+        // old-value stream of each epoch holds the value of the previous one.
+        let new_batches: Vec<_> = (1..=4)
+            .map(|i| {
+                SharedBufferBatch::for_test(
+                    transform_shared_buffer(vec![(
+                        b"a".to_vec(),
+                        HummockValue::put(Bytes::from(format!("a{}", i))),
+                    )]),
+                    epochs[i - 1],
+                    table_id,
+                )
+            })
+            .collect();
+        let old_batches: Vec<_> = (2..=4)
+            .map(|i| {
+                SharedBufferBatch::for_test(
+                    transform_shared_buffer(vec![(
+                        b"a".to_vec(),
+                        HummockValue::put(Bytes::from(format!("a{}", i - 1))),
+                    )]),
+                    epochs[i - 1],
+                    table_id,
+                )
+            })
+            .collect();
+        let mut iter = ChangeLogIter::new(
+            MergeIterator::new(new_batches.into_iter().map(|b| b.into_forward_iter())),
+            MergeIterator::new(old_batches.into_iter().map(|b| b.into_forward_iter())),
+            (Unbounded, Unbounded),
+            epochs[3],
+            epochs[1],
+            None,
+            false,
+            ChangeLogReadMode::LatestOnly,
+            1024,
+        );
+        iter.rewind().await.unwrap();
+
+        // The updates within [epoch2, epoch4] collapse into a single old→new
+        // transition between the range boundaries: from the pre-range value `a1` to
+        // the final value `a4`.
+        assert!(iter.is_valid());
+        assert_eq!(iter.key().table_key.as_ref(), b"a".as_slice());
+        assert_eq!(
+            iter.log_record().value,
+            ChangeLogValue::Update {
+                old_value: Bytes::from("a1"),
+                new_value: Bytes::from("a4"),
+            }
+        );
+        assert_eq!(iter.log_record().epoch, epochs[3]);
+        iter.next().await.unwrap();
+        assert!(!iter.is_valid());
+    }
+
+    fn encode_row(value: &str) -> HummockValue<Bytes> {
+        HummockValue::put(Bytes::from(
+            BasicSerializer.serialize(OwnedRow::new(vec![Some(ScalarImpl::Utf8(value.into()))])),
+        ))
+    }
+
+    fn varchar_reader<NI, OI>(
+        iter: ChangeLogIter<NI, OI>,
+        chunk_size: usize,
+    ) -> ChangeLogReader<NI, OI, RowDeserializer>
+    where
+        NI: HummockIterator<Direction = Forward>,
+        OI: HummockIterator<Direction = Forward>,
+    {
+        ChangeLogReader::new(
+            iter,
+            vec![DataType::Varchar],
+            chunk_size,
+            RowDeserializer::new(vec![DataType::Varchar]),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_change_log_reader() {
+        let table_id = TableId::new(1);
+        let (epoch1, epoch2) = (test_epoch(1), test_epoch(2));
+        // The same dataset as `build_change_log_iter`, with rows in value encoding: `a`
+        // and `b` are inserted at epoch1, then `a` is updated and `b` is deleted at
+        // epoch2.
+        let new1 = SharedBufferBatch::for_test(
+            transform_shared_buffer(vec![
+                (b"a".to_vec(), encode_row("a1")),
+                (b"b".to_vec(), encode_row("b1")),
+            ]),
+            epoch1,
+            table_id,
+        );
+        let new2 = SharedBufferBatch::for_test(
+            transform_shared_buffer(vec![
+                (b"a".to_vec(), encode_row("a2")),
+                (b"b".to_vec(), HummockValue::delete()),
+            ]),
+            epoch2,
+            table_id,
+        );
+        let old2 = SharedBufferBatch::for_test(
+            transform_shared_buffer(vec![
+                (b"a".to_vec(), encode_row("a1")),
+                (b"b".to_vec(), encode_row("b1")),
+            ]),
+            epoch2,
+            table_id,
+        );
+        let iter = ChangeLogIter::new(
+            MergeIterator::new(vec![new1.into_forward_iter(), new2.into_forward_iter()]),
+            old2.into_forward_iter(),
+            (Unbounded, Unbounded),
+            epoch2,
+            epoch1,
+            None,
+            false,
+            ChangeLogReadMode::All,
+            1024,
+        );
+        let mut reader = varchar_reader(iter, 4);
+        reader.init().await.unwrap();
+
+        // The first chunk fills up exactly at the chunk size, with the update of `a`
+        // encoded as an `UpdateDelete`/`UpdateInsert` pair.
+        assert_eq!(
+            reader.next_chunk().await.unwrap().unwrap(),
+            StreamChunk::from_pretty(
+                " T
+                U- a1
+                U+ a2
+                +  a1
+                -  b1",
+            )
+        );
+        // The remaining row forms a final partial chunk.
+        assert_eq!(
+            reader.next_chunk().await.unwrap().unwrap(),
+            StreamChunk::from_pretty(
+                " T
+                +  b1",
+            )
+        );
+        assert!(reader.next_chunk().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_change_log_reader_update_pair_not_split() {
+        let table_id = TableId::new(1);
+        let epoch = test_epoch(1);
+        // `a` is inserted and `b` is updated within the same epoch, so the reader sees
+        // an insert followed by an update pair.
+        let new = SharedBufferBatch::for_test(
+            transform_shared_buffer(vec![
+                (b"a".to_vec(), encode_row("a1")),
+                (b"b".to_vec(), encode_row("b2")),
+            ]),
+            epoch,
+            table_id,
+        );
+        let old = SharedBufferBatch::for_test(
+            transform_shared_buffer(vec![(b"b".to_vec(), encode_row("b1"))]),
+            epoch,
+            table_id,
+        );
+        let iter = ChangeLogIter::new(
+            new.into_forward_iter(),
+            old.into_forward_iter(),
+            (Unbounded, Unbounded),
+            epoch,
+            epoch,
+            None,
+            false,
+            ChangeLogReadMode::All,
+            1024,
+        );
+        let mut reader = varchar_reader(iter, 2);
+        reader.init().await.unwrap();
+
+        // With only one slot left for the update pair, the buffered insert is flushed
+        // early as an undersized chunk instead of splitting the pair.
+        assert_eq!(
+            reader.next_chunk().await.unwrap().unwrap(),
+            StreamChunk::from_pretty(
+                " T
+                +  a1",
+            )
+        );
+        assert_eq!(
+            reader.next_chunk().await.unwrap().unwrap(),
+            StreamChunk::from_pretty(
+                " T
+                U- b1
+                U+ b2",
+            )
+        );
+        assert!(reader.next_chunk().await.unwrap().is_none());
+    }
+
+    #[test]
+    fn test_shard_vnode_filter() {
+        let change_log = EpochNewChangeLog {
+            shards: vec![shard(0..64), shard(64..128), shard(128..256)],
+            epochs: vec![65536],
+        };
+
+        // No filter reads all shards.
+        assert_eq!(filter_shards(&change_log, None).count(), 3);
+
+        // A filter owning vnodes [32, 96) intersects the first two shards only.
+        let filter = vnode_bitmap(32..96);
+        let matched: Vec<_> = filter_shards(&change_log, Some(&filter)).collect();
+        assert_eq!(matched.len(), 2);
+        assert_eq!(matched[0], &change_log.shards[0]);
+        assert_eq!(matched[1], &change_log.shards[1]);
+
+        // A filter disjoint from all shards matches nothing.
+        let empty = vnode_bitmap([]);
+        assert_eq!(filter_shards(&change_log, Some(&empty)).count(), 0);
+    }
+}
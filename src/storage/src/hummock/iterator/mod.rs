@@ -41,6 +41,7 @@ use risingwave_hummock_sdk::EpochWithGap;
 
 use crate::hummock::iterator::HummockIteratorUnion::{First, Fourth, Second, Third};
 
+pub mod change_log;
 mod concat_delete_range_iterator;
 mod delete_range_iterator;
 mod skip_watermark;
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashSet;
 use std::future::Future;
 use std::ops::{Bound, Deref};
 use std::sync::atomic::{AtomicU64, Ordering as MemOrdering};
@@ -42,7 +43,8 @@ use crate::hummock::backup_reader::{BackupReader, BackupReaderRef};
 use crate::hummock::compactor::CompactorContext;
 use crate::hummock::event_handler::hummock_event_handler::BufferTracker;
 use crate::hummock::event_handler::{
-    HummockEvent, HummockEventHandler, HummockVersionUpdate, ReadOnlyReadVersionMapping,
+    HummockEvent, HummockEventHandler, HummockEventSender, HummockVersionUpdate,
+    ReadOnlyReadVersionMapping,
 };
 use crate::hummock::local_version::pinned_version::{start_pinned_version_worker, PinnedVersion};
 use crate::hummock::observer_manager::HummockObserverNode;
@@ -59,14 +61,14 @@ use crate::store::*;
 use crate::StateStore;
 
 struct HummockStorageShutdownGuard {
-    shutdown_sender: UnboundedSender<HummockEvent>,
+    shutdown_sender: HummockEventSender,
 }
 
 impl Drop for HummockStorageShutdownGuard {
     fn drop(&mut self) {
         let _ = self
             .shutdown_sender
-            .send(HummockEvent::Shutdown)
+            .send(HummockEvent::Shutdown(None))
             .inspect_err(|e| error!(event = ?e.0, "unable to send shutdown"));
     }
 }
@@ -78,7 +80,7 @@ impl Drop for HummockStorageShutdownGuard {
 /// Hummock is the state store backend.
 #[derive(Clone)]
 pub struct HummockStorage {
-    hummock_event_sender: UnboundedSender<HummockEvent>,
+    hummock_event_sender: HummockEventSender,
     // only used in test for setting hummock version in uploader
     _version_update_sender: UnboundedSender<HummockVersionUpdate>,
 
@@ -390,7 +392,10 @@ impl HummockStorage {
         Ok(ret)
     }
 
-    async fn new_local_inner(&self, option: NewLocalOptions) -> LocalHummockStorage {
+    async fn new_local_inner(
+        &self,
+        option: NewLocalOptions,
+    ) -> HummockResult<LocalHummockStorage> {
         let (tx, rx) = tokio::sync::oneshot::channel();
         self.hummock_event_sender
             .send(HummockEvent::RegisterReadVersion {
@@ -399,11 +404,23 @@ impl HummockStorage {
                 is_replicated: option.is_replicated,
                 vnodes: option.vnodes.clone(),
             })
-            .unwrap();
-
-        let (basic_read_version, instance_guard) = rx.await.unwrap();
+            .map_err(|_| {
+                HummockError::other(format!(
+                    "failed to register read version for table {}: the storage is shutting down",
+                    option.table_id,
+                ))
+            })?;
+
+        // The sender is dropped without a response when the event handler exits before
+        // processing the event.
+        let (basic_read_version, instance_guard) = rx.await.map_err(|_| {
+            HummockError::other(format!(
+                "failed to register read version for table {}: the storage is shutting down",
+                option.table_id,
+            ))
+        })?;
         let version_update_notifier_tx = self.version_update_notifier_tx.clone();
-        LocalHummockStorage::new(
+        Ok(LocalHummockStorage::new(
             instance_guard,
             basic_read_version,
             self.hummock_version_reader.clone(),
@@ -413,7 +430,47 @@ impl HummockStorage {
             option,
             version_update_notifier_tx,
             self.context.storage_opts.mem_table_spill_threshold,
-        )
+        ))
+    }
+
+    /// Like [`StateStore::new_local`], but returns an error instead of panicking when
+    /// the event handler has already shut down.
+    pub async fn try_new_local(
+        &self,
+        option: NewLocalOptions,
+    ) -> HummockResult<LocalHummockStorage> {
+        self.new_local_inner(option).await
+    }
+
+    /// Clear the state of the given tables only, leaving other tables untouched. The
+    /// local state stores of these tables should have been dropped before the call.
+    pub async fn clear_tables(&self, table_ids: HashSet<TableId>) {
+        if table_ids.is_empty() {
+            return;
+        }
+        let (tx, rx) = oneshot::channel();
+        self.hummock_event_sender
+            .send(HummockEvent::ClearTables {
+                table_ids,
+                notifier: tx,
+            })
+            .expect("should send success");
+        rx.await.expect("should wait success");
+    }
+
+    /// Wait until the events that were enqueued to the event handler before this call
+    /// have been processed. Mainly for debugging: a response implies that the event
+    /// handler is alive and has caught up with the backlog present at call time.
+    pub async fn drain_pending_events(&self) -> HummockResult<()> {
+        let (tx, rx) = oneshot::channel();
+        self.hummock_event_sender
+            .send(HummockEvent::DrainPending(tx))
+            .map_err(|_| {
+                HummockError::other("failed to drain pending events: the storage is shutting down")
+            })?;
+        rx.await.map_err(|_| {
+            HummockError::other("failed to drain pending events: the storage is shutting down")
+        })
     }
 
     pub fn sstable_store(&self) -> SstableStoreRef {
@@ -536,7 +593,11 @@ impl StateStore for HummockStorage {
     }
 
     fn new_local(&self, option: NewLocalOptions) -> impl Future<Output = Self::Local> + Send + '_ {
-        self.new_local_inner(option)
+        async move {
+            self.new_local_inner(option)
+                .await
+                .expect("unable to create local state store")
+        }
     }
 
     fn validate_read_epoch(&self, epoch: HummockReadEpoch) -> StorageResult<()> {
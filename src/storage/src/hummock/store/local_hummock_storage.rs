@@ -23,12 +23,13 @@ use risingwave_common::catalog::{TableId, TableOption};
 use risingwave_common::util::epoch::MAX_SPILL_TIMES;
 use risingwave_hummock_sdk::key::{is_empty_key_range, vnode_range, TableKey, TableKeyRange};
 use risingwave_hummock_sdk::{EpochWithGap, HummockEpoch};
-use tokio::sync::mpsc;
 use tracing::{warn, Instrument};
 
 use super::version::{StagingData, VersionUpdate};
 use crate::error::StorageResult;
-use crate::hummock::event_handler::{HummockEvent, HummockReadVersionRef, LocalInstanceGuard};
+use crate::hummock::event_handler::{
+    HummockEvent, HummockEventSender, HummockReadVersionRef, LocalInstanceGuard,
+};
 use crate::hummock::iterator::{
     ConcatIteratorInner, Forward, HummockIteratorUnion, MergeIterator, UserIterator,
 };
@@ -78,7 +79,7 @@ pub struct LocalHummockStorage {
     is_replicated: bool,
 
     /// Event sender.
-    event_sender: mpsc::UnboundedSender<HummockEvent>,
+    event_sender: HummockEventSender,
 
     memory_limiter: Arc<MemoryLimiter>,
 
@@ -478,7 +479,8 @@ impl LocalHummockStorage {
                     limiter.get_memory_usage()
                 );
                 self.event_sender
-                    .send(HummockEvent::BufferMayFlush)
+                    .send_data(HummockEvent::BufferMayFlush)
+                    .await
                     .expect("should be able to send");
                 let tracker = limiter
                     .require_memory(size as u64)
@@ -509,7 +511,11 @@ impl LocalHummockStorage {
             // insert imm to uploader
             if !self.is_replicated {
                 self.event_sender
-                    .send(HummockEvent::ImmToUploader(imm))
+                    .send_data(HummockEvent::ImmToUploader {
+                        imm,
+                        flush_priority: Some(imm_size as u64),
+                    })
+                    .await
                     .unwrap();
             }
             imm_size
@@ -533,7 +539,7 @@ impl LocalHummockStorage {
         instance_guard: LocalInstanceGuard,
         read_version: HummockReadVersionRef,
         hummock_version_reader: HummockVersionReader,
-        event_sender: mpsc::UnboundedSender<HummockEvent>,
+        event_sender: HummockEventSender,
         memory_limiter: Arc<MemoryLimiter>,
         write_limiter: WriteLimiterRef,
         option: NewLocalOptions,
@@ -80,6 +80,11 @@ pub struct HummockStateStoreMetrics {
 
     // block statistics
     pub block_efficiency_histogram: RelabeledHistogramVec,
+
+    // event handler
+    pub event_handler_latency: RelabeledHistogramVec,
+    pub event_handler_pending_event: IntGauge,
+    pub event_handler_on_event_counts: RelabeledCounterVec,
 }
 
 pub static GLOBAL_HUMMOCK_STATE_STORE_METRICS: OnceLock<HummockStateStoreMetrics> = OnceLock::new();
@@ -379,6 +384,41 @@ impl HummockStateStoreMetrics {
             metric_level,
         );
 
+        // 10us ~ 10s
+        let opts = histogram_opts!(
+            "state_store_event_handler_latency",
+            "Latency of processing a single event in the hummock event handler, from dequeue to \
+             completion of the synchronous handler portion",
+            exponential_buckets(0.00001, 10.0, 7).unwrap(),
+        );
+        let event_handler_latency =
+            register_histogram_vec_with_registry!(opts, &["event_type"], registry).unwrap();
+        let event_handler_latency = RelabeledHistogramVec::with_metric_level(
+            MetricLevel::Debug,
+            event_handler_latency,
+            metric_level,
+        );
+
+        let event_handler_pending_event = register_int_gauge_with_registry!(
+            "state_store_event_handler_pending_event",
+            "Number of queued events in the hummock event handler channels",
+            registry
+        )
+        .unwrap();
+
+        let event_handler_on_event_counts = register_int_counter_vec_with_registry!(
+            "state_store_event_handler_event_counts",
+            "Total number of events processed by the hummock event handler, per event variant",
+            &["event_type"],
+            registry
+        )
+        .unwrap();
+        let event_handler_on_event_counts = RelabeledCounterVec::with_metric_level(
+            MetricLevel::Debug,
+            event_handler_on_event_counts,
+            metric_level,
+        );
+
         Self {
             bloom_filter_true_negative_counts,
             bloom_filter_check_counts,
@@ -406,6 +446,10 @@ impl HummockStateStoreMetrics {
             mem_table_spill_counts,
 
             block_efficiency_histogram,
+
+            event_handler_latency,
+            event_handler_pending_event,
+            event_handler_on_event_counts,
         }
     }
 
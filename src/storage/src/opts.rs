@@ -137,6 +137,14 @@ pub struct StorageOpts {
 
     pub mem_table_spill_threshold: usize,
 
+    /// Capacity of the channel for high-volume hummock events.
+    pub hummock_event_channel_capacity: usize,
+    /// Timeout in seconds for a pending epoch sync request. 0 disables the timeout.
+    pub hummock_sync_timeout_sec: u64,
+    /// Maximum number of registered read version instances per table before
+    /// registrations are logged loudly as a suspected leak.
+    pub max_read_version_instances_per_table: usize,
+
     pub object_store_config: ObjectStoreConfig,
 }
 
@@ -255,6 +263,9 @@ impl From<(&RwConfig, &SystemParamsReader, &StorageMemoryConfig)> for StorageOpt
             enable_fast_compaction: c.storage.enable_fast_compaction,
             check_compaction_result: c.storage.check_compaction_result,
             mem_table_spill_threshold: c.storage.mem_table_spill_threshold,
+            hummock_event_channel_capacity: c.storage.hummock_event_channel_capacity,
+            hummock_sync_timeout_sec: c.storage.hummock_sync_timeout_sec,
+            max_read_version_instances_per_table: c.storage.max_read_version_instances_per_table,
             object_store_config: c.storage.object_store.clone(),
             compactor_fast_max_compact_delete_ratio: c
                 .storage
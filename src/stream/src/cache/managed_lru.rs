@@ -22,17 +22,37 @@ use std::sync::Arc;
 
 use lru::{DefaultHasher, LruCache};
 use risingwave_common::estimate_size::EstimateSize;
-use risingwave_common::metrics::LabelGuardedIntGauge;
+use risingwave_common::metrics::{LabelGuardedIntCounter, LabelGuardedIntGauge};
 use risingwave_common::util::epoch::Epoch;
 
 use crate::common::metrics::MetricsInfo;
 
 const REPORT_SIZE_EVERY_N_KB_CHANGE: usize = 4096;
 
+/// Statistics of a single eviction pass, returned by [`ManagedLruCache::evict`] and
+/// friends. Useful to diagnose whether memory pressure actually releases anything on
+/// a given operator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EvictionStats {
+    /// Number of entries dropped in this pass.
+    pub entries_evicted: u64,
+    /// Total estimated heap size of the dropped keys and values.
+    pub bytes_freed: usize,
+}
+
+/// A cached value together with the remaining pin weight of its entry.
+struct WeightedValue<V> {
+    value: V,
+    /// Remaining number of eviction passes this entry may survive after its epoch
+    /// falls below the watermark. Entries inserted by [`ManagedLruCache::put`] have
+    /// weight 0 and are evicted as usual.
+    pin_weight: usize,
+}
+
 /// The managed cache is a lru cache that bounds the memory usage by epoch.
 /// Should be used with `MemoryManager`.
 pub struct ManagedLruCache<K, V, S = DefaultHasher, A: Clone + Allocator = Global> {
-    inner: LruCache<K, V, S, A>,
+    inner: LruCache<K, WeightedValue<V>, S, A>,
     /// The entry with epoch less than water should be evicted.
     /// Should only be updated by the `MemoryManager`.
     watermark_epoch: Arc<AtomicU64>,
@@ -42,6 +62,10 @@ pub struct ManagedLruCache<K, V, S = DefaultHasher, A: Clone + Allocator = Globa
     memory_usage_metrics: LabelGuardedIntGauge<3>,
     // The metrics of evicted watermark time
     lru_evicted_watermark_time_ms: LabelGuardedIntGauge<3>,
+    /// The number of entries dropped by eviction passes
+    lru_evicted_entry_count: LabelGuardedIntCounter<3>,
+    /// The estimated bytes freed by eviction passes
+    lru_evicted_bytes: LabelGuardedIntCounter<3>,
     // Metrics info
     #[expect(dead_code)]
     metrics_info: MetricsInfo,
@@ -58,8 +82,8 @@ impl<K, V, S, A: Clone + Allocator> Drop for ManagedLruCache<K, V, S, A> {
 impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher, A: Clone + Allocator>
     ManagedLruCache<K, V, S, A>
 {
-    pub fn new_inner(
-        inner: LruCache<K, V, S, A>,
+    fn new_inner(
+        inner: LruCache<K, WeightedValue<V>, S, A>,
         watermark_epoch: Arc<AtomicU64>,
         metrics_info: MetricsInfo,
     ) -> Self {
@@ -82,36 +106,161 @@ impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher, A: Clone + Al
                 &metrics_info.desc,
             ]);
 
+        let lru_evicted_entry_count = metrics_info
+            .metrics
+            .lru_evicted_entry_count
+            .with_guarded_label_values(&[
+                &metrics_info.table_id,
+                &metrics_info.actor_id,
+                &metrics_info.desc,
+            ]);
+
+        let lru_evicted_bytes = metrics_info
+            .metrics
+            .lru_evicted_bytes
+            .with_guarded_label_values(&[
+                &metrics_info.table_id,
+                &metrics_info.actor_id,
+                &metrics_info.desc,
+            ]);
+
         Self {
             inner,
             watermark_epoch,
             kv_heap_size: 0,
             memory_usage_metrics,
             lru_evicted_watermark_time_ms,
+            lru_evicted_entry_count,
+            lru_evicted_bytes,
             metrics_info,
             last_reported_size_bytes: 0,
         }
     }
 
     /// Evict epochs lower than the watermark
-    pub fn evict(&mut self) {
-        self.evict_by_epoch(self.load_cur_epoch());
+    pub fn evict(&mut self) -> EvictionStats {
+        self.evict_by_epoch(self.load_cur_epoch())
     }
 
-    /// Evict epochs lower than the watermark, except those entry which touched in this epoch
-    pub fn evict_except_cur_epoch(&mut self) {
+    /// Evict epochs lower than the watermark, except those entry which touched in this epoch.
+    ///
+    /// "Touched in this epoch" is relative to the epoch last passed to
+    /// [`Self::update_epoch`]: even if the watermark has advanced past it, entries
+    /// read or written since that call survive this pass.
+    pub fn evict_except_cur_epoch(&mut self) -> EvictionStats {
         let epoch = min(self.load_cur_epoch(), self.inner.current_epoch());
-        self.evict_by_epoch(epoch);
+        self.evict_by_epoch(epoch)
     }
 
     /// Evict epochs lower than the watermark
-    fn evict_by_epoch(&mut self, epoch: u64) {
-        while let Some((key, value)) = self.inner.pop_lru_by_epoch(epoch) {
-            self.kv_heap_size_dec(key.estimated_size() + value.estimated_size());
-        }
+    fn evict_by_epoch(&mut self, epoch: u64) -> EvictionStats {
+        let stats = self.pop_lru_by_epoch(epoch);
         self.report_evicted_watermark_time(epoch);
+        self.report_eviction_stats(&stats);
+        // Eviction passes are exactly the moments a memory regression is debugged, so
+        // refresh the gauge unconditionally instead of waiting for the change
+        // threshold.
+        self.report_memory_usage_forced();
+        stats
     }
 
+    fn pop_lru_by_epoch(&mut self, epoch: u64) -> EvictionStats {
+        let mut stats = EvictionStats::default();
+        let mut pinned = Vec::new();
+        while let Some((key, mut value)) = self.inner.pop_lru_by_epoch(epoch) {
+            if value.pin_weight > 0 {
+                // Pinned entries survive this pass at the cost of one weight unit,
+                // so sustained pressure still releases them eventually.
+                value.pin_weight -= 1;
+                pinned.push((key, value));
+                continue;
+            }
+            let size = key.estimated_size() + value.value.estimated_size();
+            self.kv_heap_size_dec(size);
+            stats.entries_evicted += 1;
+            stats.bytes_freed += size;
+        }
+        for (key, value) in pinned {
+            self.inner.push(key, value);
+        }
+        stats
+    }
+
+    /// Evict entries whose recorded epoch is older than `now - ttl` in physical time,
+    /// regardless of the memory-pressure watermark.
+    ///
+    /// The recorded epoch of an entry is the cache epoch at its last touch. For caches
+    /// driven by barriers via [`Self::update_epoch`] this is the epoch of the barrier
+    /// preceding the touch; for caches created with [`new_unbounded_with_wall_clock_epochs`]
+    /// it approximates the wall-clock time of the touch.
+    pub fn evict_by_ttl(&mut self, ttl: std::time::Duration) -> EvictionStats {
+        let cutoff = Epoch::from_physical_time(
+            Epoch::physical_now().saturating_sub(ttl.as_millis() as u64),
+        );
+        let stats = self.pop_lru_by_epoch(cutoff.0);
+        self.report_eviction_stats(&stats);
+        self.report_memory_usage_forced();
+        // Advance the cache epoch so that entries touched from now on record the
+        // current time, keeping subsequent TTL passes meaningful.
+        let now = Epoch::now();
+        if self.inner.current_epoch() < now.0 {
+            self.inner.update_epoch(now.0);
+        }
+        stats
+    }
+
+    /// Evict the entry with the given key immediately, regardless of the watermark.
+    ///
+    /// This is for operators that know a cached entry is invalid (e.g. the group key
+    /// was deleted) and don't want to keep serving it until the next watermark pass.
+    /// The size accounting is kept consistent with the removal.
+    pub fn evict_key(&mut self, k: &K) -> Option<V> {
+        let value = self.inner.pop(k);
+        value.map(|value| {
+            self.kv_heap_size_dec(k.estimated_size() + value.value.estimated_size());
+            value.value
+        })
+    }
+
+    /// Remove all entries for which `f` returns `false`, e.g. to purge cached rows of
+    /// a vnode that moved away during scaling, without waiting for epoch eviction.
+    /// The size accounting is kept consistent, also for values mutated by `f`.
+    ///
+    /// Note that this rebuilds the cache, so the retained entries are re-recorded
+    /// under the current cache epoch, though their recency order is preserved.
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+        let mut retained = Vec::with_capacity(self.inner.len());
+        while let Some((key, mut value)) = self.inner.pop_lru() {
+            let old_size = key.estimated_size() + value.value.estimated_size();
+            if f(&key, &mut value.value) {
+                let new_size = key.estimated_size() + value.value.estimated_size();
+                if new_size >= old_size {
+                    self.kv_heap_size_inc(new_size - old_size);
+                } else {
+                    self.kv_heap_size_dec(old_size - new_size);
+                }
+                retained.push((key, value));
+            } else {
+                self.kv_heap_size_dec(old_size);
+            }
+        }
+        // Re-insert in LRU-to-MRU order to preserve the recency order.
+        for (key, value) in retained {
+            self.inner.put(key, value);
+        }
+    }
+
+    /// Advance the epoch that new touches are recorded under. The owning executor
+    /// should call this with `barrier.epoch.curr` on every barrier: entries are
+    /// stamped on *access* (including pure reads via [`Self::get`]), not only on
+    /// insertion, so a read-heavy operator that rarely inserts still keeps its hot
+    /// entries ahead of the eviction watermark. Without this call, the cache epoch
+    /// stays where it was and every entry eventually falls below the watermark no
+    /// matter how recently it was read.
+    ///
+    /// This is also what defines the entries spared by
+    /// [`Self::evict_except_cur_epoch`]: anything touched since the last
+    /// `update_epoch` call counts as "current epoch" there.
     pub fn update_epoch(&mut self, epoch: u64) {
         self.inner.update_epoch(epoch);
     }
@@ -123,24 +272,62 @@ impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher, A: Clone + Al
     /// An iterator visiting all values in most-recently used order. The iterator element type is
     /// &V.
     pub fn values(&self) -> impl Iterator<Item = &V> {
-        self.inner.iter().map(|(_k, v)| v)
+        self.inner.iter().map(|(_k, v)| &v.value)
     }
 
     pub fn put(&mut self, k: K, v: V) -> Option<V> {
+        self.insert_with_weight(k, v, 0)
+    }
+
+    /// Like [`Self::put`], but pins the entry with the given weight: the entry
+    /// survives up to `weight` eviction passes after its epoch falls below the
+    /// watermark, consuming one weight unit per pass. Use this for values that are
+    /// far more expensive to recompute than others, e.g. a fully materialized
+    /// window.
+    pub fn insert_with_weight(&mut self, k: K, v: V, weight: usize) -> Option<V> {
         let key_size = k.estimated_size();
         self.kv_heap_size_inc(key_size + v.estimated_size());
-        let old_val = self.inner.put(k, v);
-        if let Some(old_val) = &old_val {
-            self.kv_heap_size_dec(key_size + old_val.estimated_size());
+        let old_val = self.inner.put(
+            k,
+            WeightedValue {
+                value: v,
+                pin_weight: weight,
+            },
+        );
+        old_val.map(|old_val| {
+            self.kv_heap_size_dec(key_size + old_val.value.estimated_size());
+            old_val.value
+        })
+    }
+
+    /// Insert entries in bulk, e.g. when hydrating the cache from a state-table scan.
+    /// The size accounting is accumulated and reported once at the end, instead of
+    /// per entry like [`Self::put`].
+    pub fn extend(&mut self, iter: impl IntoIterator<Item = (K, V)>) {
+        let mut inc = 0;
+        let mut dec = 0;
+        for (k, v) in iter {
+            let key_size = k.estimated_size();
+            inc += key_size + v.estimated_size();
+            if let Some(old_val) = self.inner.put(
+                k,
+                WeightedValue {
+                    value: v,
+                    pin_weight: 0,
+                },
+            ) {
+                dec += key_size + old_val.value.estimated_size();
+            }
         }
-        old_val
+        self.kv_heap_size = self.kv_heap_size.saturating_add(inc).saturating_sub(dec);
+        self.report_memory_usage();
     }
 
     pub fn get_mut(&mut self, k: &K) -> Option<MutGuard<'_, V>> {
         let v = self.inner.get_mut(k);
         v.map(|inner| {
             MutGuard::new(
-                inner,
+                &mut inner.value,
                 &mut self.kv_heap_size,
                 &mut self.last_reported_size_bytes,
                 &mut self.memory_usage_metrics,
@@ -153,22 +340,28 @@ impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher, A: Clone + Al
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        self.inner.get(k)
+        self.inner.get(k).map(|v| &v.value)
     }
 
+    /// Peek the value of the given key without updating the LRU order, unlike
+    /// [`Self::get`]. An entry that is only peeked keeps the epoch of its last real
+    /// access, so it stays eligible for eviction. Use this to inspect a value for
+    /// metrics or validation without making it "hot".
     pub fn peek<Q>(&self, k: &Q) -> Option<&V>
     where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        self.inner.peek(k)
+        self.inner.peek(k).map(|v| &v.value)
     }
 
+    /// Mutable version of [`Self::peek`]. The size accounting is still updated when
+    /// the value is mutated through the returned guard.
     pub fn peek_mut(&mut self, k: &K) -> Option<MutGuard<'_, V>> {
         let v = self.inner.peek_mut(k);
         v.map(|inner| {
             MutGuard::new(
-                inner,
+                &mut inner.value,
                 &mut self.kv_heap_size,
                 &mut self.last_reported_size_bytes,
                 &mut self.memory_usage_metrics,
@@ -179,12 +372,18 @@ impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher, A: Clone + Al
     pub fn push(&mut self, k: K, v: V) -> Option<(K, V)> {
         self.kv_heap_size_inc(k.estimated_size() + v.estimated_size());
 
-        let old_kv = self.inner.push(k, v);
-
-        if let Some((old_key, old_val)) = &old_kv {
-            self.kv_heap_size_dec(old_key.estimated_size() + old_val.estimated_size());
-        }
-        old_kv
+        let old_kv = self.inner.push(
+            k,
+            WeightedValue {
+                value: v,
+                pin_weight: 0,
+            },
+        );
+
+        old_kv.map(|(old_key, old_val)| {
+            self.kv_heap_size_dec(old_key.estimated_size() + old_val.value.estimated_size());
+            (old_key, old_val.value)
+        })
     }
 
     pub fn contains<Q>(&self, k: &Q) -> bool
@@ -203,6 +402,14 @@ impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher, A: Clone + Al
         self.inner.len() == 0
     }
 
+    /// The estimated heap size of all cached keys and values, i.e. the bytes this
+    /// cache currently holds. This is the same accounting that backs the
+    /// `stream_memory_usage` gauge, but exact rather than throttled by the reporting
+    /// threshold.
+    pub fn estimated_size(&self) -> usize {
+        self.kv_heap_size
+    }
+
     pub fn clear(&mut self) {
         self.inner.clear();
     }
@@ -229,6 +436,19 @@ impl<K: Hash + Eq + EstimateSize, V: EstimateSize, S: BuildHasher, A: Clone + Al
         }
     }
 
+    /// Report the current size to the gauge regardless of the change threshold.
+    fn report_memory_usage_forced(&mut self) {
+        self.memory_usage_metrics.set(self.kv_heap_size as _);
+        self.last_reported_size_bytes = self.kv_heap_size;
+    }
+
+    fn report_eviction_stats(&self, stats: &EvictionStats) {
+        if stats.entries_evicted > 0 {
+            self.lru_evicted_entry_count.inc_by(stats.entries_evicted);
+            self.lru_evicted_bytes.inc_by(stats.bytes_freed as _);
+        }
+    }
+
     fn report_evicted_watermark_time(&self, epoch: u64) {
         self.lru_evicted_watermark_time_ms
             .set(Epoch(epoch).physical_time() as _);
@@ -246,6 +466,19 @@ pub fn new_unbounded<K: Hash + Eq + EstimateSize, V: EstimateSize>(
     ManagedLruCache::new_inner(LruCache::unbounded(), watermark_epoch, metrics_info)
 }
 
+/// Like [`new_unbounded`], but initializes the cache epoch from the wall clock, so that
+/// entries record a physically meaningful epoch on insertion even if the owner never
+/// drives [`ManagedLruCache::update_epoch`] with barrier epochs. Use this for caches
+/// that are evicted by [`ManagedLruCache::evict_by_ttl`].
+pub fn new_unbounded_with_wall_clock_epochs<K: Hash + Eq + EstimateSize, V: EstimateSize>(
+    watermark_epoch: Arc<AtomicU64>,
+    metrics_info: MetricsInfo,
+) -> ManagedLruCache<K, V> {
+    let mut cache = new_unbounded(watermark_epoch, metrics_info);
+    cache.update_epoch(Epoch::now().0);
+    cache
+}
+
 pub fn new_with_hasher_in<
     K: Hash + Eq + EstimateSize,
     V: EstimateSize,
@@ -339,3 +572,232 @@ impl<'a, V: EstimateSize> DerefMut for MutGuard<'a, V> {
         self.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use risingwave_common::util::epoch::test_epoch;
+
+    use super::*;
+
+    #[test]
+    fn test_evict_key() {
+        let mut cache: ManagedLruCache<String, String> =
+            new_unbounded(Arc::new(AtomicU64::new(0)), MetricsInfo::for_test());
+
+        cache.put("k1".to_string(), "value 1".to_string());
+        cache.put("k2".to_string(), "value 2".to_string());
+        let size_before = cache.kv_heap_size;
+
+        let evicted = cache.evict_key(&"k1".to_string());
+        assert_eq!(evicted, Some("value 1".to_string()));
+        assert!(!cache.contains(&"k1".to_string()));
+        assert!(cache.contains(&"k2".to_string()));
+        assert_eq!(
+            cache.kv_heap_size,
+            size_before
+                - "k1".to_string().estimated_size()
+                - "value 1".to_string().estimated_size()
+        );
+
+        // Evicting a non-existent key is a no-op.
+        assert_eq!(cache.evict_key(&"k1".to_string()), None);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_extend() {
+        let mut cache: ManagedLruCache<String, String> =
+            new_unbounded(Arc::new(AtomicU64::new(0)), MetricsInfo::for_test());
+        cache.put("k1".to_string(), "value 1".to_string());
+
+        cache.extend([
+            ("k2".to_string(), "value 2".to_string()),
+            ("k1".to_string(), "value 1 updated".to_string()),
+        ]);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.peek("k1"), Some(&"value 1 updated".to_string()));
+        assert_eq!(cache.peek("k2"), Some(&"value 2".to_string()));
+
+        // The bulk size accounting must match inserting the same entries one by one.
+        let mut expected: ManagedLruCache<String, String> =
+            new_unbounded(Arc::new(AtomicU64::new(0)), MetricsInfo::for_test());
+        expected.put("k1".to_string(), "value 1 updated".to_string());
+        expected.put("k2".to_string(), "value 2".to_string());
+        assert_eq!(cache.kv_heap_size, expected.kv_heap_size);
+    }
+
+    #[test]
+    fn test_peek_does_not_update_recency() {
+        let watermark = Arc::new(AtomicU64::new(0));
+        let mut cache: ManagedLruCache<String, String> =
+            new_unbounded(watermark.clone(), MetricsInfo::for_test());
+
+        cache.update_epoch(test_epoch(1));
+        cache.put("k1".to_string(), "value 1".to_string());
+        cache.put("k2".to_string(), "value 2".to_string());
+        cache.update_epoch(test_epoch(2));
+
+        // `peek` must not mark the entry as touched in the current epoch...
+        assert_eq!(cache.peek("k1"), Some(&"value 1".to_string()));
+        // ...while `get` does.
+        assert_eq!(cache.get("k2"), Some(&"value 2".to_string()));
+
+        watermark.store(test_epoch(2), Ordering::Relaxed);
+        cache.evict();
+        assert!(!cache.contains(&"k1".to_string()));
+        assert!(cache.contains(&"k2".to_string()));
+    }
+
+    #[test]
+    fn test_update_epoch_protects_reads() {
+        let watermark = Arc::new(AtomicU64::new(0));
+        let mut cache: ManagedLruCache<String, String> =
+            new_unbounded(watermark.clone(), MetricsInfo::for_test());
+
+        cache.update_epoch(test_epoch(1));
+        cache.put("read".to_string(), "value 1".to_string());
+        cache.put("idle".to_string(), "value 2".to_string());
+
+        // A pure read after the epoch advances re-stamps the entry, even though
+        // nothing is inserted in the new epoch.
+        cache.update_epoch(test_epoch(2));
+        assert_eq!(cache.get("read"), Some(&"value 1".to_string()));
+
+        // The watermark races ahead of the cache epoch; entries touched since the
+        // last `update_epoch` still survive an `evict_except_cur_epoch` pass.
+        watermark.store(test_epoch(3), Ordering::Relaxed);
+        cache.evict_except_cur_epoch();
+        assert!(cache.contains(&"read".to_string()));
+        assert!(!cache.contains(&"idle".to_string()));
+    }
+
+    #[test]
+    fn test_insert_with_weight() {
+        let watermark = Arc::new(AtomicU64::new(0));
+        let mut cache: ManagedLruCache<String, String> =
+            new_unbounded(watermark.clone(), MetricsInfo::for_test());
+
+        cache.update_epoch(test_epoch(1));
+        cache.put("cheap".to_string(), "value 1".to_string());
+        cache.insert_with_weight("expensive".to_string(), "value 2".to_string(), 1);
+        cache.update_epoch(test_epoch(2));
+
+        // The pinned entry survives the first pass below the watermark.
+        watermark.store(test_epoch(2), Ordering::Relaxed);
+        let stats = cache.evict();
+        assert_eq!(stats.entries_evicted, 1);
+        assert!(!cache.contains(&"cheap".to_string()));
+        assert!(cache.contains(&"expensive".to_string()));
+
+        // The weight is consumed, so the next pass releases the entry.
+        cache.update_epoch(test_epoch(3));
+        watermark.store(test_epoch(3), Ordering::Relaxed);
+        let stats = cache.evict();
+        assert_eq!(stats.entries_evicted, 1);
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut cache: ManagedLruCache<String, String> =
+            new_unbounded(Arc::new(AtomicU64::new(0)), MetricsInfo::for_test());
+        cache.put("k1".to_string(), "value 1".to_string());
+        cache.put("k2".to_string(), "value 2".to_string());
+        cache.put("k3".to_string(), "value 3".to_string());
+        let size_before = cache.kv_heap_size;
+
+        cache.retain(|k, _v| k != "k2");
+        assert_eq!(cache.len(), 2);
+        assert!(cache.contains(&"k1".to_string()));
+        assert!(!cache.contains(&"k2".to_string()));
+        assert!(cache.contains(&"k3".to_string()));
+        assert_eq!(
+            cache.kv_heap_size,
+            size_before
+                - "k2".to_string().estimated_size()
+                - "value 2".to_string().estimated_size()
+        );
+
+        // Values mutated by the predicate are re-accounted.
+        cache.retain(|_k, v| {
+            v.push_str(" longer");
+            true
+        });
+        assert_eq!(cache.len(), 2);
+        assert_eq!(
+            cache.kv_heap_size,
+            "k1".to_string().estimated_size()
+                + "k3".to_string().estimated_size()
+                + cache.peek("k1").unwrap().estimated_size()
+                + cache.peek("k3").unwrap().estimated_size()
+        );
+    }
+
+    #[test]
+    fn test_eviction_stats() {
+        let watermark = Arc::new(AtomicU64::new(0));
+        let mut cache: ManagedLruCache<String, String> =
+            new_unbounded(watermark.clone(), MetricsInfo::for_test());
+
+        cache.update_epoch(test_epoch(1));
+        cache.put("k1".to_string(), "value 1".to_string());
+        cache.put("k2".to_string(), "value 2".to_string());
+        cache.update_epoch(test_epoch(2));
+        let size_before = cache.kv_heap_size;
+
+        watermark.store(test_epoch(2), Ordering::Relaxed);
+        let stats = cache.evict();
+        assert_eq!(stats.entries_evicted, 2);
+        assert_eq!(stats.bytes_freed, size_before - cache.kv_heap_size);
+
+        // An eviction pass that frees nothing reports empty stats.
+        assert_eq!(cache.evict(), EvictionStats::default());
+    }
+
+    #[test]
+    fn test_estimated_size() {
+        let watermark = Arc::new(AtomicU64::new(0));
+        let mut cache: ManagedLruCache<String, String> =
+            new_unbounded(watermark.clone(), MetricsInfo::for_test());
+        assert_eq!(cache.estimated_size(), 0);
+
+        cache.update_epoch(test_epoch(1));
+        cache.put("k1".to_string(), "value 1".to_string());
+        cache.put("k2".to_string(), "value 2".to_string());
+        assert_eq!(
+            cache.estimated_size(),
+            "k1".to_string().estimated_size()
+                + "k2".to_string().estimated_size()
+                + "value 1".to_string().estimated_size()
+                + "value 2".to_string().estimated_size()
+        );
+
+        cache.update_epoch(test_epoch(2));
+        watermark.store(test_epoch(2), Ordering::Relaxed);
+        cache.evict();
+        assert_eq!(cache.estimated_size(), 0);
+    }
+
+    #[test]
+    fn test_evict_by_ttl() {
+        let mut cache: ManagedLruCache<String, String> = new_unbounded_with_wall_clock_epochs(
+            Arc::new(AtomicU64::new(0)),
+            MetricsInfo::for_test(),
+        );
+
+        // Simulate an entry inserted 10 seconds ago.
+        let now = Epoch::physical_now();
+        cache.update_epoch(Epoch::from_physical_time(now - 10_000).0);
+        cache.put("old".to_string(), "value 1".to_string());
+        cache.update_epoch(Epoch::from_physical_time(now).0);
+        cache.put("new".to_string(), "value 2".to_string());
+
+        cache.evict_by_ttl(std::time::Duration::from_secs(5));
+        assert!(!cache.contains(&"old".to_string()));
+        assert!(cache.contains(&"new".to_string()));
+
+        // Entries within the TTL are kept.
+        cache.evict_by_ttl(std::time::Duration::from_secs(3600));
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -20,6 +20,17 @@ pub use top_n::*;
 mod ordered;
 mod top_n;
 
+/// Coverage of a synced [`StateCache`] over the rows in the state table, in key order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheCoverage<K> {
+    /// The cache holds all rows.
+    Full,
+    /// The cache holds only a prefix of the rows: keys greater than the contained
+    /// bound (the last cached key, or any key when `None`) may exist in the state
+    /// table without being cached.
+    Prefix(Option<K>),
+}
+
 /// A common interface for state table cache.
 pub trait StateCache: EstimateSize {
     type Key: Ord + EstimateSize;
@@ -53,6 +64,10 @@ pub trait StateCache: EstimateSize {
 
     /// Get the reference of first key-value pair in the cache.
     fn first_key_value(&self) -> Option<(&Self::Key, &Self::Value)>;
+
+    /// Coverage of the cache over the rows in the state table, used to bound the scan
+    /// of uncached rows. Should only be called when the cache is synced.
+    fn coverage(&self) -> CacheCoverage<&Self::Key>;
 }
 
 pub trait StateCacheFiller {
@@ -17,7 +17,7 @@ use std::collections::BTreeMap;
 use risingwave_common::array::Op;
 use risingwave_common::estimate_size::{EstimateSize, KvSize};
 
-use super::{StateCache, StateCacheFiller};
+use super::{CacheCoverage, StateCache, StateCacheFiller};
 
 /// An implementation of [`StateCache`] that uses a [`BTreeMap`] as the underlying cache, with no
 /// capacity limit.
@@ -130,6 +130,12 @@ impl<K: Ord + EstimateSize, V: EstimateSize> StateCache for OrderedStateCache<K,
         assert!(self.synced);
         self.cache.first_key_value()
     }
+
+    fn coverage(&self) -> CacheCoverage<&Self::Key> {
+        assert!(self.synced);
+        // The cache is unbounded, so once synced it holds all rows.
+        CacheCoverage::Full
+    }
 }
 
 impl<K: Ord + EstimateSize, V: EstimateSize> StateCacheFiller for &mut OrderedStateCache<K, V> {
@@ -15,8 +15,8 @@
 use risingwave_common::array::Op;
 use risingwave_common::estimate_size::EstimateSize;
 
-use super::{StateCache, StateCacheFiller};
-use crate::common::cache::TopNCache;
+use super::{CacheCoverage, StateCache, StateCacheFiller};
+use crate::common::cache::{CacheCapacity, TopNCache};
 
 /// An implementation of [`StateCache`] that uses a [`TopNCache`] as the underlying cache, with
 /// limited capacity.
@@ -29,9 +29,13 @@ pub struct TopNStateCache<K: Ord + EstimateSize, V: EstimateSize> {
 
 impl<K: Ord + EstimateSize, V: EstimateSize> TopNStateCache<K, V> {
     pub fn new(capacity: usize) -> Self {
+        Self::with_capacity(CacheCapacity::Rows(capacity))
+    }
+
+    pub fn with_capacity(capacity: CacheCapacity) -> Self {
         Self {
             table_row_count: None,
-            cache: TopNCache::new(capacity),
+            cache: TopNCache::with_capacity(capacity),
             synced: false,
         }
     }
@@ -87,7 +91,7 @@ impl<K: Ord + EstimateSize, V: EstimateSize> TopNStateCache<K, V> {
         old_val
     }
 
-    pub fn capacity_inner(&self) -> usize {
+    pub fn capacity_inner(&self) -> CacheCapacity {
         self.cache.capacity()
     }
 
@@ -163,6 +167,18 @@ impl<K: Ord + EstimateSize, V: EstimateSize> StateCache for TopNStateCache<K, V>
         assert!(self.synced);
         self.cache.first_key_value()
     }
+
+    fn coverage(&self) -> CacheCoverage<&Self::Key> {
+        assert!(self.synced);
+        if self.row_count_matched() {
+            CacheCoverage::Full
+        } else {
+            // Without a known table row count, we can't tell whether there are rows
+            // beyond the cached prefix, so conservatively report prefix coverage up to
+            // the last cached key.
+            CacheCoverage::Prefix(self.cache.last_key())
+        }
+    }
 }
 
 impl<K: Ord + EstimateSize, V: EstimateSize> StateCacheFiller for &mut TopNStateCache<K, V> {
@@ -170,7 +186,9 @@ impl<K: Ord + EstimateSize, V: EstimateSize> StateCacheFiller for &mut TopNState
     type Value = V;
 
     fn capacity(&self) -> Option<usize> {
-        Some(self.capacity_inner())
+        // A byte-bounded cache has no row limit to cut off the fill at, so it reports
+        // `None` and relies on eviction during insertion.
+        self.capacity_inner().row_limit()
     }
 
     fn insert_unchecked(&mut self, key: Self::Key, value: Self::Value) {
@@ -16,11 +16,42 @@ use std::collections::BTreeMap;
 
 use risingwave_common::estimate_size::{EstimateSize, KvSize};
 
+/// Capacity bound of a cache: either a maximum number of entries, or a maximum
+/// estimated heap size in bytes.
+///
+/// A row-count bound treats every entry as equally expensive, which is a poor fit for
+/// aggregations holding variable-sized values (e.g. `string_agg`). A byte bound evicts
+/// based on the [`EstimateSize`] of the entries instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheCapacity {
+    Rows(usize),
+    Bytes(usize),
+}
+
+impl CacheCapacity {
+    /// The maximum number of entries, if the capacity is expressed in rows.
+    pub fn row_limit(&self) -> Option<usize> {
+        match self {
+            Self::Rows(n) => Some(*n),
+            Self::Bytes(_) => None,
+        }
+    }
+
+    /// Whether a cache of `len` entries with `heap_size` estimated bytes exceeds the
+    /// capacity.
+    fn is_exceeded(&self, len: usize, heap_size: usize) -> bool {
+        match self {
+            Self::Rows(n) => len > *n,
+            Self::Bytes(b) => heap_size > *b,
+        }
+    }
+}
+
 /// Inner top-N cache structure for [`super::TopNStateCache`].
 #[derive(Clone)]
 pub struct TopNCache<K: Ord + EstimateSize, V: EstimateSize> {
     /// The capacity of the cache.
-    capacity: usize,
+    capacity: CacheCapacity,
     /// Ordered cache entries.
     entries: BTreeMap<K, V>,
     kv_heap_size: KvSize,
@@ -35,9 +66,14 @@ impl<K: Ord + EstimateSize, V: EstimateSize> EstimateSize for TopNCache<K, V> {
 }
 
 impl<K: Ord + EstimateSize, V: EstimateSize> TopNCache<K, V> {
-    /// Create a new cache with specified capacity and order requirements.
+    /// Create a new cache with specified row capacity and order requirements.
     /// To create a cache with unlimited capacity, use `usize::MAX` for `capacity`.
     pub fn new(capacity: usize) -> Self {
+        Self::with_capacity(CacheCapacity::Rows(capacity))
+    }
+
+    /// Create a new cache bounded by the given [`CacheCapacity`].
+    pub fn with_capacity(capacity: CacheCapacity) -> Self {
         Self {
             capacity,
             entries: Default::default(),
@@ -46,7 +82,7 @@ impl<K: Ord + EstimateSize, V: EstimateSize> TopNCache<K, V> {
     }
 
     /// Get the capacity of the cache.
-    pub fn capacity(&self) -> usize {
+    pub fn capacity(&self) -> CacheCapacity {
         self.capacity
     }
 
@@ -77,7 +113,15 @@ impl<K: Ord + EstimateSize, V: EstimateSize> TopNCache<K, V> {
             self.kv_heap_size.sub_val(old_val);
         }
         // evict if capacity is reached
-        while self.entries.len() > self.capacity {
+        while self
+            .capacity
+            .is_exceeded(self.entries.len(), self.kv_heap_size.size())
+        {
+            if matches!(self.capacity, CacheCapacity::Bytes(_)) && self.entries.len() <= 1 {
+                // With a byte bound, always keep at least one entry so that a single
+                // oversized entry doesn't render the cache permanently empty.
+                break;
+            }
             if let Some((key, val)) = self.entries.pop_last() {
                 self.kv_heap_size.sub(&key, &val);
             }
@@ -129,7 +173,7 @@ mod tests {
     #[test]
     fn test_top_n_cache() {
         let mut cache = TopNCache::new(3);
-        assert_eq!(cache.capacity(), 3);
+        assert_eq!(cache.capacity(), CacheCapacity::Rows(3));
         assert_eq!(cache.len(), 0);
         assert!(cache.is_empty());
         assert!(cache.first_key_value().is_none());
@@ -160,7 +204,7 @@ mod tests {
         );
 
         cache.insert(0, "foo".to_string());
-        assert_eq!(cache.capacity(), 3);
+        assert_eq!(cache.capacity(), CacheCapacity::Rows(3));
         assert_eq!(cache.len(), 3);
         assert_eq!(cache.first_key(), Some(&0));
         assert_eq!(cache.last_key(), Some(&3));
@@ -184,8 +228,32 @@ mod tests {
 
         cache.clear();
         assert_eq!(cache.len(), 0);
-        assert_eq!(cache.capacity(), 3);
+        assert_eq!(cache.capacity(), CacheCapacity::Rows(3));
         assert_eq!(cache.first_key(), None);
         assert_eq!(cache.last_key(), None);
     }
+
+    #[test]
+    fn test_top_n_cache_byte_bounded() {
+        // Each entry takes 10 bytes of estimated heap size (the `String` payload).
+        let entry = |c: char| c.to_string().repeat(10);
+        let mut cache = TopNCache::with_capacity(CacheCapacity::Bytes(25));
+        assert_eq!(cache.capacity(), CacheCapacity::Bytes(25));
+        assert_eq!(cache.capacity().row_limit(), None);
+
+        cache.insert(1, entry('a'));
+        cache.insert(2, entry('b'));
+        assert_eq!(cache.len(), 2);
+
+        // Inserting a third entry exceeds the byte budget, evicting the largest key.
+        cache.insert(0, entry('c'));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.first_key(), Some(&0));
+        assert_eq!(cache.last_key(), Some(&1));
+
+        // A single oversized entry is kept rather than leaving the cache empty.
+        let mut cache = TopNCache::with_capacity(CacheCapacity::Bytes(5));
+        cache.insert(1, entry('a'));
+        assert_eq!(cache.len(), 1);
+    }
 }
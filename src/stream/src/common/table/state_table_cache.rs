@@ -17,7 +17,7 @@ use risingwave_common::estimate_size::EstimateSize;
 use risingwave_common::row::{OwnedRow, Row, RowExt};
 use risingwave_common::types::{DefaultOrdered, ScalarRefImpl};
 
-use crate::common::cache::{StateCache, TopNStateCache};
+use crate::common::cache::{CacheCoverage, StateCache, TopNStateCache};
 
 /// The watermark cache key is just an `OwnedRow` wrapped in `DefaultOrdered`.
 /// This is because we want to use the `DefaultOrdered` implementation of `Ord`.
@@ -178,6 +178,10 @@ impl StateCache for StateTableWatermarkCache {
     fn first_key_value(&self) -> Option<(&Self::Key, &Self::Value)> {
         self.inner.first_key_value()
     }
+
+    fn coverage(&self) -> CacheCoverage<&Self::Key> {
+        self.inner.coverage()
+    }
 }
 
 #[cfg(test)]
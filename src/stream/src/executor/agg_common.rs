@@ -16,6 +16,7 @@ use std::collections::HashMap;
 
 use risingwave_expr::aggregate::AggCall;
 use risingwave_pb::stream_plan::PbAggNodeVersion;
+use risingwave_storage::store::PrefetchOptions;
 use risingwave_storage::StateStore;
 
 use super::aggregation::AggStateStorage;
@@ -35,6 +36,8 @@ pub struct AggExecutorArgs<S: StateStore, E: AggExecutorExtraArgs> {
 
     // system configs
     pub extreme_cache_size: usize,
+    pub extreme_cache_key_size_limit: Option<usize>,
+    pub state_sync_prefetch: Option<PrefetchOptions>,
 
     // agg common things
     pub agg_calls: Vec<AggCall>,
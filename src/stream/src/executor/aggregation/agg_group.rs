@@ -26,12 +26,13 @@ use risingwave_common::row::{OwnedRow, Row, RowExt};
 use risingwave_common::util::iter_util::ZipEqFast;
 use risingwave_expr::aggregate::{AggCall, BoxedAggregateFunction};
 use risingwave_pb::stream_plan::PbAggNodeVersion;
+use risingwave_storage::store::PrefetchOptions;
 use risingwave_storage::StateStore;
 
 use super::agg_state::{AggState, AggStateStorage};
 use crate::common::table::state_table::StateTable;
 use crate::executor::error::StreamExecutorResult;
-use crate::executor::PkIndices;
+use crate::executor::{ActorContextRef, PkIndices};
 
 pub trait Strategy {
     /// Infer the change type of the aggregation result. Don't need to take the ownership of
@@ -202,7 +203,10 @@ impl<S: StateStore, Strtg: Strategy> AggGroup<S, Strtg> {
         pk_indices: &PkIndices,
         row_count_index: usize,
         extreme_cache_size: usize,
+        extreme_cache_key_size_limit: Option<usize>,
+        state_sync_prefetch: Option<PrefetchOptions>,
         input_schema: &Schema,
+        ctx: &ActorContextRef,
     ) -> StreamExecutorResult<Self> {
         let encoded_states = intermediate_state_table
             .get_row(group_key.as_ref().map(GroupKey::table_pk))
@@ -221,7 +225,10 @@ impl<S: StateStore, Strtg: Strategy> AggGroup<S, Strtg> {
                 encoded_states.as_ref().map(|outputs| &outputs[idx]),
                 pk_indices,
                 extreme_cache_size,
+                extreme_cache_key_size_limit,
+                state_sync_prefetch,
                 input_schema,
+                ctx,
             )?;
             states.push(state);
         }
@@ -254,7 +261,10 @@ impl<S: StateStore, Strtg: Strategy> AggGroup<S, Strtg> {
         pk_indices: &PkIndices,
         row_count_index: usize,
         extreme_cache_size: usize,
+        extreme_cache_key_size_limit: Option<usize>,
+        state_sync_prefetch: Option<PrefetchOptions>,
         input_schema: &Schema,
+        ctx: &ActorContextRef,
     ) -> StreamExecutorResult<Self> {
         let mut states = Vec::with_capacity(agg_calls.len());
         for (idx, (agg_call, agg_func)) in agg_calls.iter().zip_eq_fast(agg_funcs).enumerate() {
@@ -266,7 +276,10 @@ impl<S: StateStore, Strtg: Strategy> AggGroup<S, Strtg> {
                 Some(&encoded_states[idx]),
                 pk_indices,
                 extreme_cache_size,
+                extreme_cache_key_size_limit,
+                state_sync_prefetch,
                 input_schema,
+                ctx,
             )?;
             states.push(state);
         }
@@ -21,13 +21,16 @@ use risingwave_common::types::Datum;
 use risingwave_common::util::sort_util::ColumnOrder;
 use risingwave_expr::aggregate::{AggCall, AggregateState, BoxedAggregateFunction};
 use risingwave_pb::stream_plan::PbAggNodeVersion;
+use risingwave_storage::store::PrefetchOptions;
 use risingwave_storage::StateStore;
 
 use super::minput::MaterializedInputState;
 use super::GroupKey;
+use crate::common::cache::CacheCapacity;
+use crate::common::metrics::MetricsInfo;
 use crate::common::table::state_table::StateTable;
 use crate::common::StateTableColumnMapping;
-use crate::executor::{PkIndices, StreamExecutorResult};
+use crate::executor::{ActorContextRef, PkIndices, StreamExecutorResult};
 
 /// Represents the persistent storage of aggregation state.
 pub enum AggStateStorage<S: StateStore> {
@@ -75,7 +78,10 @@ impl AggState {
         encoded_state: Option<&Datum>,
         pk_indices: &PkIndices,
         extreme_cache_size: usize,
+        extreme_cache_key_size_limit: Option<usize>,
+        state_sync_prefetch: Option<PrefetchOptions>,
         input_schema: &Schema,
+        ctx: &ActorContextRef,
     ) -> StreamExecutorResult<Self> {
         Ok(match storage {
             AggStateStorage::Value => {
@@ -86,18 +92,31 @@ impl AggState {
                 Self::Value(state)
             }
             AggStateStorage::MaterializedInput {
+                table,
                 mapping,
                 order_columns,
-                ..
-            } => Self::MaterializedInput(Box::new(MaterializedInputState::new(
-                version,
-                agg_call,
-                pk_indices,
-                order_columns,
-                mapping,
-                extreme_cache_size,
-                input_schema,
-            )?)),
+            } => {
+                let metrics_info = MetricsInfo::new(
+                    ctx.streaming_metrics.clone(),
+                    table.table_id(),
+                    ctx.id,
+                    "agg state cache",
+                );
+                Self::MaterializedInput(Box::new(MaterializedInputState::new(
+                    version,
+                    agg_call,
+                    pk_indices,
+                    order_columns,
+                    false, // plans always order `min`/`max` by the agg value column
+                    mapping,
+                    CacheCapacity::Rows(extreme_cache_size),
+                    extreme_cache_key_size_limit,
+                    state_sync_prefetch,
+                    None, // derive the output chunk size from the argument types
+                    metrics_info,
+                    input_schema,
+                )?))
+            }
         })
     }
 
@@ -23,10 +23,10 @@ use risingwave_common::util::memcmp_encoding::MemcmpEncoded;
 use risingwave_common::util::row_serde::OrderedRowSerde;
 use smallvec::SmallVec;
 
-use crate::common::cache::{StateCache, StateCacheFiller};
+use crate::common::cache::{CacheCoverage, StateCache, StateCacheFiller};
 
 /// Cache key type.
-type CacheKey = MemcmpEncoded;
+pub type CacheKey = MemcmpEncoded;
 
 #[derive(Debug)]
 pub struct CacheValue(SmallVec<[Datum; 2]>);
@@ -53,6 +53,15 @@ pub trait AggStateCache: EstimateSize {
 
     /// Output the first value.
     fn output_first(&self) -> Datum;
+
+    /// Output the first non-null value. Returns `None` if no cached entry has a
+    /// non-null first argument, which does not imply the state table has none when
+    /// the cache is bounded.
+    fn output_first_non_null(&self) -> Datum;
+
+    /// Coverage of the cache over the state table rows of the group. When only a
+    /// prefix is cached, rows beyond it must be read from the state table.
+    fn coverage(&self) -> CacheCoverage<CacheKey>;
 }
 
 /// Trait that defines agg state cache syncing interface.
@@ -152,6 +161,19 @@ where
         let value = self.state_cache.values().next()?;
         value.0[0].clone()
     }
+
+    fn output_first_non_null(&self) -> Datum {
+        self.state_cache
+            .values()
+            .find_map(|value| value.0[0].clone())
+    }
+
+    fn coverage(&self) -> CacheCoverage<CacheKey> {
+        match self.state_cache.coverage() {
+            CacheCoverage::Full => CacheCoverage::Full,
+            CacheCoverage::Prefix(key) => CacheCoverage::Prefix(key.cloned()),
+        }
+    }
 }
 
 pub struct GenericAggStateCacheFiller<'filler, C>
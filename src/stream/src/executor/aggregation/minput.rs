@@ -14,28 +14,57 @@
 
 use std::ops::Bound::{self};
 
+use futures::future::try_join_all;
 use futures::{pin_mut, StreamExt};
 use futures_async_stream::for_await;
 use itertools::Itertools;
 use risingwave_common::array::StreamChunk;
 use risingwave_common::catalog::Schema;
 use risingwave_common::estimate_size::EstimateSize;
-use risingwave_common::row::{OwnedRow, RowExt};
-use risingwave_common::types::Datum;
+use risingwave_common::metrics::LabelGuardedIntCounter;
+use risingwave_common::row::{OwnedRow, Row, RowExt};
+use risingwave_common::types::{DataType, Datum, ToOwnedDatum};
+use risingwave_common::util::chunk_coalesce::DataChunkBuilder;
 use risingwave_common::util::row_serde::OrderedRowSerde;
 use risingwave_common::util::sort_util::{ColumnOrder, OrderType};
-use risingwave_expr::aggregate::{AggCall, AggKind, BoxedAggregateFunction};
+use risingwave_expr::aggregate::{AggCall, AggKind, AggregateState, BoxedAggregateFunction};
 use risingwave_pb::stream_plan::PbAggNodeVersion;
 use risingwave_storage::store::PrefetchOptions;
 use risingwave_storage::StateStore;
 
-use super::agg_state_cache::{AggStateCache, GenericAggStateCache};
+use super::agg_state_cache::{AggStateCache, CacheKey, GenericAggStateCache};
 use super::GroupKey;
-use crate::common::cache::{OrderedStateCache, TopNStateCache};
+use crate::common::cache::{CacheCapacity, CacheCoverage, TopNStateCache};
+use crate::common::metrics::MetricsInfo;
 use crate::common::table::state_table::StateTable;
 use crate::common::StateTableColumnMapping;
 use crate::executor::{PkIndices, StreamExecutorResult};
 
+/// Default size of the chunks produced when computing the output from materialized input.
+const CHUNK_SIZE: usize = 1024;
+
+/// Decide the output chunk size from the argument types. Variable-width arguments
+/// (e.g. large text fed to `string_agg`) are replayed in smaller batches so that a
+/// single chunk cannot hold megabytes of payload, while fixed-width arguments keep
+/// the full [`CHUNK_SIZE`] to amortize per-chunk overhead.
+fn adaptive_chunk_size(arg_data_types: &[DataType]) -> usize {
+    let variable_width = arg_data_types.iter().any(|ty| {
+        matches!(
+            ty,
+            DataType::Varchar
+                | DataType::Bytea
+                | DataType::Jsonb
+                | DataType::List(_)
+                | DataType::Struct(_)
+        )
+    });
+    if variable_width {
+        CHUNK_SIZE / 4
+    } else {
+        CHUNK_SIZE
+    }
+}
+
 /// Aggregation state as a materialization of input chunks.
 ///
 /// For example, in `string_agg`, several useful columns are picked from input chunks and
@@ -61,20 +90,66 @@ pub struct MaterializedInputState {
     /// Whether to output the first value from cache.
     output_first_value: bool,
 
+    /// Whether to deduplicate rows by the distinct argument when computing the output,
+    /// for `DISTINCT` aggregations whose state table may hold duplicate values.
+    distinct_dedup: bool,
+
+    /// Whether to skip `NULL` values when outputting the first value, for
+    /// `first_value`/`last_value` with `IGNORE NULLS`.
+    ignore_nulls: bool,
+
     /// Serializer for cache key.
     #[estimate_size(ignore)]
     cache_key_serializer: OrderedRowSerde,
+
+    /// Max byte size of a serialized cache key. Groups containing a key exceeding the
+    /// limit are not cached and fall back to state-table reads. `None` means unlimited.
+    max_cache_key_size: Option<usize>,
+
+    /// Prefetch options for the cold-start sync scan of the state table. `None` derives
+    /// the options from the cache capacity: prefetch only when the fill is unbounded.
+    #[estimate_size(ignore)]
+    sync_prefetch: Option<PrefetchOptions>,
+
+    /// Size of the chunks replayed through the aggregate function when computing the
+    /// output. Derived from the argument type widths unless overridden.
+    output_chunk_size: usize,
+
+    /// Data types of the argument columns, for building output chunks without the cache.
+    arg_data_types: Vec<DataType>,
+
+    /// Counter of cold-start cache syncs from the state table.
+    #[estimate_size(ignore)]
+    cache_sync_count: LabelGuardedIntCounter<3>,
+
+    /// Counter of outputs served from the synced cache.
+    #[estimate_size(ignore)]
+    cache_hit_count: LabelGuardedIntCounter<3>,
 }
 
 impl MaterializedInputState {
     /// Create an instance from [`AggCall`].
+    ///
+    /// `min`/`max` implicitly order by the agg value column, so that the extreme value
+    /// is always in the first row in cache-key order. Setting `order_by_surrogate`
+    /// lifts this requirement and takes the given `order_columns` as-is, allowing a
+    /// cheaper surrogate key to be used as the state table pk and cache key when the
+    /// agg value is wide (e.g. `max` over a huge text column). The caller must
+    /// guarantee that the surrogate order is consistent with the agg value order,
+    /// otherwise the output is not the extreme value.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         version: PbAggNodeVersion,
         agg_call: &AggCall,
         pk_indices: &PkIndices,
         order_columns: &[ColumnOrder],
+        order_by_surrogate: bool,
         col_mapping: &StateTableColumnMapping,
-        extreme_cache_size: usize,
+        extreme_cache_capacity: CacheCapacity,
+        max_cache_key_size: Option<usize>,
+        sync_prefetch: Option<PrefetchOptions>,
+        output_chunk_size: Option<usize>,
+        metrics_info: MetricsInfo,
         input_schema: &Schema,
     ) -> StreamExecutorResult<Self> {
         if agg_call.distinct && version < PbAggNodeVersion::Issue12140 {
@@ -84,6 +159,7 @@ impl MaterializedInputState {
         }
 
         let arg_col_indices = agg_call.args.val_indices().to_vec();
+        let arg_data_types = agg_call.args.arg_types().to_vec();
 
         let (order_col_indices, order_types) = if version < PbAggNodeVersion::Issue13465 {
             generate_order_columns_before_version_issue_13465(
@@ -98,6 +174,23 @@ impl MaterializedInputState {
                 .unzip()
         };
 
+        if matches!(agg_call.kind, AggKind::Min | AggKind::Max) && !order_by_surrogate {
+            // The output of `min`/`max` is read from the first row in cache-key order,
+            // which is the extreme value only when the rows are ordered by the agg
+            // value column. A surrogate order key may replace it only when explicitly
+            // opted in via `order_by_surrogate`.
+            let order_by_value = order_col_indices.first() == Some(&arg_col_indices[0])
+                && order_types.first().map_or(false, |o| match agg_call.kind {
+                    AggKind::Min => o.is_ascending(),
+                    _ => o.is_descending(),
+                });
+            assert!(
+                order_by_value,
+                "`{}` must order by the agg value column unless `order_by_surrogate` is set",
+                agg_call.kind
+            );
+        }
+
         // map argument columns to state table column indices
         let state_table_arg_col_indices = arg_col_indices
             .iter()
@@ -127,15 +220,20 @@ impl MaterializedInputState {
         let cache: Box<dyn AggStateCache + Send + Sync> = match agg_call.kind {
             AggKind::Min | AggKind::Max | AggKind::FirstValue | AggKind::LastValue => {
                 Box::new(GenericAggStateCache::new(
-                    TopNStateCache::new(extreme_cache_size),
+                    TopNStateCache::with_capacity(extreme_cache_capacity),
                     agg_call.args.arg_types(),
                 ))
             }
             AggKind::StringAgg
             | AggKind::ArrayAgg
             | AggKind::JsonbAgg
-            | AggKind::JsonbObjectAgg => Box::new(GenericAggStateCache::new(
-                OrderedStateCache::new(),
+            | AggKind::JsonbObjectAgg
+            // ordered-set aggregates, the fraction is a direct argument baked into `func`
+            | AggKind::PercentileCont
+            | AggKind::PercentileDisc => Box::new(GenericAggStateCache::new(
+                // The cache keeps a bounded prefix of the rows in cache-key order;
+                // rows spilled beyond it are re-read from the state table on output.
+                TopNStateCache::with_capacity(extreme_cache_capacity),
                 agg_call.args.arg_types(),
             )),
             _ => panic!(
@@ -147,6 +245,29 @@ impl MaterializedInputState {
             agg_call.kind,
             AggKind::Min | AggKind::Max | AggKind::FirstValue | AggKind::LastValue
         );
+        // For first-value-style aggregations, `DISTINCT` does not change the result.
+        let distinct_dedup = agg_call.distinct && !output_first_value;
+
+        let agg_kind_label = agg_call.kind.to_string();
+        let cache_sync_count = metrics_info
+            .metrics
+            .agg_state_cache_sync_count
+            .with_guarded_label_values(&[
+                &metrics_info.table_id,
+                &metrics_info.actor_id,
+                &agg_kind_label,
+            ]);
+        let cache_hit_count = metrics_info
+            .metrics
+            .agg_state_cache_hit_count
+            .with_guarded_label_values(&[
+                &metrics_info.table_id,
+                &metrics_info.actor_id,
+                &agg_kind_label,
+            ]);
+
+        let output_chunk_size =
+            output_chunk_size.unwrap_or_else(|| adaptive_chunk_size(&arg_data_types));
 
         Ok(Self {
             arg_col_indices,
@@ -155,10 +276,23 @@ impl MaterializedInputState {
             state_table_order_col_indices,
             cache,
             output_first_value,
+            distinct_dedup,
+            ignore_nulls: agg_call.ignore_nulls,
             cache_key_serializer,
+            max_cache_key_size,
+            sync_prefetch,
+            output_chunk_size,
+            arg_data_types,
+            cache_sync_count,
+            cache_hit_count,
         })
     }
 
+    #[cfg(test)]
+    fn cache_is_synced(&self) -> bool {
+        self.cache.is_synced()
+    }
+
     /// Apply a chunk of data to the state cache.
     pub fn apply_chunk(&mut self, chunk: &StreamChunk) -> StreamExecutorResult<()> {
         self.cache.apply_batch(
@@ -178,7 +312,157 @@ impl MaterializedInputState {
         func: &BoxedAggregateFunction,
     ) -> StreamExecutorResult<Datum> {
         if !self.cache.is_synced() {
-            let mut cache_filler = self.cache.begin_syncing();
+            self.cache_sync_count.inc();
+            if !self.try_sync_cache(state_table, group_key).await? {
+                // A serialized cache key exceeded `max_cache_key_size`, so the group is
+                // not cached. Serve this output directly from the state table.
+                return self.get_output_from_table(state_table, group_key, func).await;
+            }
+        } else {
+            self.cache_hit_count.inc();
+        }
+        assert!(self.cache.is_synced());
+
+        if self.output_first_value {
+            // special case for `min`, `max`, `first_value` and `last_value`
+            // take the first value from the cache
+            if self.ignore_nulls {
+                match self.cache.output_first_non_null() {
+                    Some(value) => Ok(Some(value)),
+                    // The first non-null value may lie beyond the cached window when all
+                    // cached entries are null, so fall back to the state table.
+                    None => self.get_output_from_table(state_table, group_key, func).await,
+                }
+            } else {
+                Ok(self.cache.output_first())
+            }
+        } else if self.distinct_dedup {
+            self.get_distinct_output(state_table, group_key, func).await
+        } else {
+            let chunks = self
+                .cache
+                .output_batches(self.output_chunk_size)
+                .collect_vec();
+            let mut state = func.create_state();
+            for chunk in chunks {
+                func.update(&mut state, &chunk).await?;
+            }
+            // When the bounded cache holds only a prefix of the rows, fold in the
+            // spilled suffix from the state table, scanning only beyond the cache
+            // boundary instead of re-reading the whole group.
+            if let CacheCoverage::Prefix(last_cached_key) = self.cache.coverage() {
+                self.update_state_from_table(
+                    state_table,
+                    group_key,
+                    func,
+                    &mut state,
+                    last_cached_key.as_ref(),
+                )
+                .await?;
+            }
+            Ok(func.get_result(&state).await?)
+        }
+    }
+
+    /// Proactively sync the cache from the state table, e.g. during executor
+    /// init/recovery, so that the first `get_output` after a failover doesn't pay the
+    /// cold-start scan cost. Skips the sync if the cache is already synced, and
+    /// respects the cache capacity and `max_cache_key_size` like a regular sync.
+    pub async fn prefetch(
+        &mut self,
+        state_table: &StateTable<impl StateStore>,
+        group_key: Option<&GroupKey>,
+    ) -> StreamExecutorResult<()> {
+        if !self.cache.is_synced() {
+            self.cache_sync_count.inc();
+            // An oversized cache key leaves the cache unsynced, same as in
+            // `get_output`.
+            let _ = self.try_sync_cache(state_table, group_key).await?;
+        }
+        Ok(())
+    }
+
+    /// Get the outputs for multiple group keys in one pass.
+    ///
+    /// The state cache is bound to a single group, so the outputs are computed directly
+    /// from the state table, issuing the per-group range scans concurrently to cut
+    /// state-store round-trips. The single-key [`Self::get_output`] should still be
+    /// used on the hot path where the group's cache applies.
+    pub async fn get_outputs(
+        &self,
+        state_table: &StateTable<impl StateStore>,
+        group_keys: &[GroupKey],
+        func: &BoxedAggregateFunction,
+    ) -> StreamExecutorResult<Vec<Datum>> {
+        try_join_all(
+            group_keys
+                .iter()
+                .map(|group_key| self.get_output_from_table(state_table, Some(group_key), func)),
+        )
+        .await
+    }
+
+    /// Sync the cache from the state table. Returns `false` if syncing is abandoned
+    /// because a serialized cache key exceeds `max_cache_key_size`, in which case the
+    /// cache is left unsynced.
+    async fn try_sync_cache(
+        &mut self,
+        state_table: &StateTable<impl StateStore>,
+        group_key: Option<&GroupKey>,
+    ) -> StreamExecutorResult<bool> {
+        let mut cache_filler = self.cache.begin_syncing();
+        let sub_range: &(Bound<OwnedRow>, Bound<OwnedRow>) = &(Bound::Unbounded, Bound::Unbounded);
+        let all_data_iter = state_table
+            .iter_with_prefix(
+                group_key.map(GroupKey::table_pk),
+                sub_range,
+                self.sync_prefetch.unwrap_or(PrefetchOptions {
+                    prefetch: cache_filler.capacity().is_none(),
+                    for_large_query: false,
+                }),
+            )
+            .await?;
+        pin_mut!(all_data_iter);
+
+        #[for_await]
+        for keyed_row in all_data_iter.take(cache_filler.capacity().unwrap_or(usize::MAX)) {
+            let state_row = keyed_row?;
+            let cache_key = {
+                let mut cache_key = Vec::new();
+                self.cache_key_serializer.serialize(
+                    state_row
+                        .as_ref()
+                        .project(&self.state_table_order_col_indices),
+                    &mut cache_key,
+                );
+                if self
+                    .max_cache_key_size
+                    .map_or(false, |max_size| cache_key.len() > max_size)
+                {
+                    return Ok(false);
+                }
+                cache_key.into()
+            };
+            let cache_value = self
+                .state_table_arg_col_indices
+                .iter()
+                .map(|i| state_row[*i].clone())
+                .collect();
+            cache_filler.append(cache_key, cache_value);
+        }
+        cache_filler.finish();
+        Ok(true)
+    }
+
+    /// Compute the output by a full scan of the state table, bypassing the cache. Used
+    /// when the group cannot be cached due to an oversized cache key.
+    async fn get_output_from_table(
+        &self,
+        state_table: &StateTable<impl StateStore>,
+        group_key: Option<&GroupKey>,
+        func: &BoxedAggregateFunction,
+    ) -> StreamExecutorResult<Datum> {
+        if self.output_first_value {
             let sub_range: &(Bound<OwnedRow>, Bound<OwnedRow>) =
                 &(Bound::Unbounded, Bound::Unbounded);
             let all_data_iter = state_table
@@ -186,51 +470,189 @@ impl MaterializedInputState {
                     group_key.map(GroupKey::table_pk),
                     sub_range,
                     PrefetchOptions {
-                        prefetch: cache_filler.capacity().is_none(),
+                        prefetch: true,
                         for_large_query: false,
                     },
                 )
                 .await?;
             pin_mut!(all_data_iter);
 
+            // rows are ordered by the cache key, so the output is in the first row,
+            // or in the first row with a non-null argument under `IGNORE NULLS`
             #[for_await]
-            for keyed_row in all_data_iter.take(cache_filler.capacity().unwrap_or(usize::MAX)) {
+            for keyed_row in all_data_iter {
                 let state_row = keyed_row?;
-                let cache_key = {
-                    let mut cache_key = Vec::new();
-                    self.cache_key_serializer.serialize(
-                        state_row
-                            .as_ref()
-                            .project(&self.state_table_order_col_indices),
-                        &mut cache_key,
-                    );
-                    cache_key.into()
-                };
-                let cache_value = self
-                    .state_table_arg_col_indices
-                    .iter()
-                    .map(|i| state_row[*i].clone())
-                    .collect();
-                cache_filler.append(cache_key, cache_value);
+                let value = state_row[self.state_table_arg_col_indices[0]].clone();
+                if value.is_some() || !self.ignore_nulls {
+                    return Ok(value);
+                }
             }
-            cache_filler.finish();
-        }
-        assert!(self.cache.is_synced());
-
-        if self.output_first_value {
-            // special case for `min`, `max`, `first_value` and `last_value`
-            // take the first value from the cache
-            Ok(self.cache.output_first())
+            Ok(None)
         } else {
-            const CHUNK_SIZE: usize = 1024;
-            let chunks = self.cache.output_batches(CHUNK_SIZE).collect_vec();
             let mut state = func.create_state();
-            for chunk in chunks {
-                func.update(&mut state, &chunk).await?;
+            if self.distinct_dedup {
+                let mut builder =
+                    DataChunkBuilder::new(self.arg_data_types.clone(), self.output_chunk_size);
+                let mut prev_distinct = None;
+                self.update_state_from_table_distinct(
+                    state_table,
+                    group_key,
+                    func,
+                    &mut state,
+                    &mut builder,
+                    &mut prev_distinct,
+                    None,
+                )
+                .await?;
+                if let Some(chunk) = builder.consume_all() {
+                    func.update(&mut state, &chunk.into()).await?;
+                }
+            } else {
+                self.update_state_from_table(state_table, group_key, func, &mut state, None)
+                    .await?;
             }
             Ok(func.get_result(&state).await?)
         }
     }
+
+    /// Compute the output for a `DISTINCT` aggregation from the cached prefix plus the
+    /// spilled suffix. Rows sharing the distinct argument are adjacent in cache-key
+    /// order, so deduplicating against the previous distinct value suffices, and that
+    /// value is carried across the cache boundary so duplicates spanning it are not
+    /// double-counted.
+    async fn get_distinct_output(
+        &self,
+        state_table: &StateTable<impl StateStore>,
+        group_key: Option<&GroupKey>,
+        func: &BoxedAggregateFunction,
+    ) -> StreamExecutorResult<Datum> {
+        let mut state = func.create_state();
+        let mut builder = DataChunkBuilder::new(self.arg_data_types.clone(), self.output_chunk_size);
+        let mut prev_distinct: Option<Datum> = None;
+        for chunk in self
+            .cache
+            .output_batches(self.output_chunk_size)
+            .collect_vec()
+        {
+            for (_, row) in chunk.rows() {
+                let distinct_value = row.datum_at(0).to_owned_datum();
+                if prev_distinct.as_ref() == Some(&distinct_value) {
+                    continue;
+                }
+                if let Some(chunk) = builder.append_one_row(row) {
+                    func.update(&mut state, &chunk.into()).await?;
+                }
+                prev_distinct = Some(distinct_value);
+            }
+        }
+        if let CacheCoverage::Prefix(last_cached_key) = self.cache.coverage() {
+            self.update_state_from_table_distinct(
+                state_table,
+                group_key,
+                func,
+                &mut state,
+                &mut builder,
+                &mut prev_distinct,
+                last_cached_key.as_ref(),
+            )
+            .await?;
+        }
+        if let Some(chunk) = builder.consume_all() {
+            func.update(&mut state, &chunk.into()).await?;
+        }
+        Ok(func.get_result(&state).await?)
+    }
+
+    /// Fold the rows of the group whose cache key is greater than `start_after` (all
+    /// rows when `None`) from the state table into `state`, in cache-key order.
+    async fn update_state_from_table(
+        &self,
+        state_table: &StateTable<impl StateStore>,
+        group_key: Option<&GroupKey>,
+        func: &BoxedAggregateFunction,
+        state: &mut AggregateState,
+        start_after: Option<&CacheKey>,
+    ) -> StreamExecutorResult<()> {
+        let start_bound = match start_after {
+            // The cache key is the memcmp-encoded order columns, i.e. the state table
+            // pk after the group key, so it decodes into a scan bound directly.
+            Some(key) => Bound::Excluded(self.cache_key_serializer.deserialize(key)?),
+            None => Bound::Unbounded,
+        };
+        let sub_range = (start_bound, Bound::<OwnedRow>::Unbounded);
+        let all_data_iter = state_table
+            .iter_with_prefix(
+                group_key.map(GroupKey::table_pk),
+                &sub_range,
+                PrefetchOptions {
+                    prefetch: true,
+                    for_large_query: false,
+                },
+            )
+            .await?;
+        pin_mut!(all_data_iter);
+
+        let mut builder = DataChunkBuilder::new(self.arg_data_types.clone(), self.output_chunk_size);
+        #[for_await]
+        for keyed_row in all_data_iter {
+            let state_row = keyed_row?;
+            let arg_row = state_row.as_ref().project(&self.state_table_arg_col_indices);
+            if let Some(chunk) = builder.append_one_row(arg_row) {
+                func.update(state, &chunk.into()).await?;
+            }
+        }
+        if let Some(chunk) = builder.consume_all() {
+            func.update(state, &chunk.into()).await?;
+        }
+        Ok(())
+    }
+
+    /// Like [`Self::update_state_from_table`], but deduplicates rows whose distinct
+    /// argument equals the previous one, continuing from the caller's `prev_distinct`
+    /// and partially filled `builder`. The final partial chunk is left in `builder`.
+    #[allow(clippy::too_many_arguments)]
+    async fn update_state_from_table_distinct(
+        &self,
+        state_table: &StateTable<impl StateStore>,
+        group_key: Option<&GroupKey>,
+        func: &BoxedAggregateFunction,
+        state: &mut AggregateState,
+        builder: &mut DataChunkBuilder,
+        prev_distinct: &mut Option<Datum>,
+        start_after: Option<&CacheKey>,
+    ) -> StreamExecutorResult<()> {
+        let start_bound = match start_after {
+            Some(key) => Bound::Excluded(self.cache_key_serializer.deserialize(key)?),
+            None => Bound::Unbounded,
+        };
+        let sub_range = (start_bound, Bound::<OwnedRow>::Unbounded);
+        let all_data_iter = state_table
+            .iter_with_prefix(
+                group_key.map(GroupKey::table_pk),
+                &sub_range,
+                PrefetchOptions {
+                    prefetch: true,
+                    for_large_query: false,
+                },
+            )
+            .await?;
+        pin_mut!(all_data_iter);
+
+        #[for_await]
+        for keyed_row in all_data_iter {
+            let state_row = keyed_row?;
+            let distinct_value = state_row[self.state_table_arg_col_indices[0]].clone();
+            if prev_distinct.as_ref() == Some(&distinct_value) {
+                continue;
+            }
+            let arg_row = state_row.as_ref().project(&self.state_table_arg_col_indices);
+            if let Some(chunk) = builder.append_one_row(arg_row) {
+                func.update(state, &chunk.into()).await?;
+            }
+            *prev_distinct = Some(distinct_value);
+        }
+        Ok(())
+    }
 }
 
 /// Copied from old code before <https://github.com/risingwavelabs/risingwave/commit/0020507edbc4010b20aeeb560c7bea9159315602>.
@@ -274,6 +696,16 @@ fn generate_order_columns_before_version_issue_13465(
             order_col_indices.push(agg_call.args.val_indices()[0]);
             order_types.push(OrderType::ascending());
         }
+        // The distinct key alone deduplicates values but does not make the order total
+        // when the order-by columns are not unique per distinct value, e.g.
+        // `array_agg(distinct x order by y)`. Append the primary keys as tie-breakers
+        // so that the materialized order is deterministic.
+        for pk_index in pk_indices {
+            if !order_col_indices.contains(pk_index) {
+                order_col_indices.push(*pk_index);
+                order_types.push(OrderType::ascending());
+            }
+        }
     } else {
         // If not distinct, we need to materialize input with the primary keys
         let pk_len = pk_indices.len();
@@ -291,19 +723,22 @@ mod tests {
     use itertools::Itertools;
     use rand::seq::IteratorRandom;
     use rand::Rng;
-    use risingwave_common::array::StreamChunk;
+    use risingwave_common::array::{Op, StreamChunk};
     use risingwave_common::catalog::{ColumnDesc, ColumnId, Field, Schema, TableId};
     use risingwave_common::row::OwnedRow;
     use risingwave_common::test_prelude::StreamChunkTestExt;
-    use risingwave_common::types::{DataType, ListValue};
+    use risingwave_common::types::{DataType, ListValue, ScalarImpl, StructType, StructValue};
     use risingwave_common::util::epoch::{test_epoch, EpochPair};
     use risingwave_common::util::sort_util::{ColumnOrder, OrderType};
     use risingwave_expr::aggregate::{build_append_only, AggCall};
+    use risingwave_expr::expr::LiteralExpression;
     use risingwave_pb::stream_plan::PbAggNodeVersion;
     use risingwave_storage::memory::MemoryStateStore;
     use risingwave_storage::StateStore;
 
     use super::MaterializedInputState;
+    use crate::common::cache::CacheCapacity;
+    use crate::common::metrics::MetricsInfo;
     use crate::common::table::state_table::StateTable;
     use crate::common::StateTableColumnMapping;
     use crate::executor::aggregation::GroupKey;
@@ -345,6 +780,168 @@ mod tests {
         (table, mapping)
     }
 
+    #[tokio::test]
+    async fn test_percentile_cont_state() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: float8, _row_id: int64)
+
+        let field1 = Field::unnamed(DataType::Float64);
+        let field2 = Field::unnamed(DataType::Int64);
+        let input_schema = Schema::new(vec![field1, field2]);
+
+        let mut agg_call = AggCall::from_pretty("(percentile_cont:float8 $0:float8 orderby $0:asc)");
+        agg_call.direct_args = vec![LiteralExpression::new(
+            DataType::Float64,
+            Some(0.5f64.into()),
+        )];
+        let agg = build_append_only(&agg_call).unwrap();
+        let group_key = None;
+
+        let (mut table, mapping) = create_mem_state_table(
+            &input_schema,
+            vec![0, 1],
+            vec![OrderType::ascending(), OrderType::ascending()],
+        )
+        .await;
+
+        let order_columns = vec![
+            ColumnOrder::new(0, OrderType::ascending()), // a ASC within group
+            ColumnOrder::new(1, OrderType::ascending()), // _row_id
+        ];
+        let mut state = MaterializedInputState::new(
+            PbAggNodeVersion::Max,
+            &agg_call,
+            &PkIndices::new(), // unused
+            &order_columns,
+            false,
+            &mapping,
+            CacheCapacity::Rows(1024),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
+            &input_schema,
+        )
+        .unwrap();
+
+        let mut epoch = EpochPair::new_test_epoch(test_epoch(1));
+        table.init_epoch(epoch);
+
+        {
+            let chunk = create_chunk(
+                " F I
+                + 1.0 101
+                + 2.0 102
+                + 3.0 103",
+                &mut table,
+                &mapping,
+            );
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res, Some(2.0f64.into()));
+        }
+
+        {
+            let chunk = create_chunk(
+                " F I
+                + 4.0 104",
+                &mut table,
+                &mapping,
+            );
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            // interpolates between the two middle values
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res, Some(2.5f64.into()));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_extreme_agg_state_oversized_cache_key() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: varchar, b: int32, c: int32, _row_id: int64)
+
+        let field1 = Field::unnamed(DataType::Varchar);
+        let field2 = Field::unnamed(DataType::Int32);
+        let field3 = Field::unnamed(DataType::Int32);
+        let field4 = Field::unnamed(DataType::Int64);
+        let input_schema = Schema::new(vec![field1, field2, field3, field4]);
+
+        let agg_call = AggCall::from_pretty("(min:varchar $0:varchar)"); // min(a)
+        let agg = build_append_only(&agg_call).unwrap();
+        let group_key = None;
+
+        let (mut table, mapping) = create_mem_state_table(
+            &input_schema,
+            vec![0, 3],
+            vec![
+                OrderType::ascending(), // for AggKind::Min
+                OrderType::ascending(),
+            ],
+        )
+        .await;
+
+        let order_columns = vec![
+            ColumnOrder::new(0, OrderType::ascending()), // a ASC for AggKind::Min
+            ColumnOrder::new(3, OrderType::ascending()), // _row_id
+        ];
+        let mut state = MaterializedInputState::new(
+            PbAggNodeVersion::Max,
+            &agg_call,
+            &PkIndices::new(), // unused
+            &order_columns,
+            false,
+            &mapping,
+            CacheCapacity::Rows(usize::MAX),
+            Some(16), // max cache key size in bytes
+            None,
+            None,
+            MetricsInfo::for_test(),
+            &input_schema,
+        )
+        .unwrap();
+
+        let mut epoch = EpochPair::new_test_epoch(test_epoch(1));
+        table.init_epoch(epoch);
+
+        let oversized = "z".repeat(100);
+        let chunk = create_chunk(
+            &format!(
+                " T i i I
+                + a 1 8 123
+                + {oversized} 5 2 128"
+            ),
+            &mut table,
+            &mapping,
+        );
+        state.apply_chunk(&chunk)?;
+
+        epoch.inc_for_test();
+        table.commit(epoch).await.unwrap();
+
+        // The oversized order-column value exceeds the cache key limit, so the group is
+        // not cached, but the output is still correct via direct state-table reads.
+        let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+        assert_eq!(res, Some("a".into()));
+        assert!(!state.cache_is_synced());
+
+        // Subsequent outputs keep falling back to the state table.
+        let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+        assert_eq!(res, Some("a".into()));
+        assert!(!state.cache_is_synced());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_extreme_agg_state_basic_min() -> StreamExecutorResult<()> {
         // Assumption of input schema:
@@ -379,8 +976,13 @@ mod tests {
             &agg_call,
             &PkIndices::new(), // unused
             &order_columns,
+            false,
             &mapping,
-            usize::MAX,
+            CacheCapacity::Rows(usize::MAX),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
             &input_schema,
         )
         .unwrap();
@@ -433,8 +1035,13 @@ mod tests {
                 &agg_call,
                 &PkIndices::new(), // unused
                 &order_columns,
+                false,
                 &mapping,
-                usize::MAX,
+                CacheCapacity::Rows(usize::MAX),
+                None,
+                None,
+                None,
+                MetricsInfo::for_test(),
                 &input_schema,
             )
             .unwrap();
@@ -479,8 +1086,13 @@ mod tests {
             &agg_call,
             &PkIndices::new(), // unused
             &order_columns,
+            false,
             &mapping,
-            usize::MAX,
+            CacheCapacity::Rows(usize::MAX),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
             &input_schema,
         )
         .unwrap();
@@ -533,8 +1145,13 @@ mod tests {
                 &agg_call,
                 &PkIndices::new(), // unused
                 &order_columns,
+                false,
                 &mapping,
-                usize::MAX,
+                CacheCapacity::Rows(usize::MAX),
+                None,
+                None,
+                None,
+                MetricsInfo::for_test(),
                 &input_schema,
             )
             .unwrap();
@@ -547,35 +1164,146 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_extreme_agg_state_with_hidden_input() -> StreamExecutorResult<()> {
+    async fn test_extreme_agg_state_surrogate_order() -> StreamExecutorResult<()> {
         // Assumption of input schema:
-        // (a: varchar, b: int32, c: int32, _row_id: int64)
+        // (a: varchar, b: int32, _row_id: int64)
+        // where `b` is a surrogate key whose order is consistent with `a`'s order,
+        // so `max(a)` can order by `b` instead of indexing the wide `a` column.
 
         let field1 = Field::unnamed(DataType::Varchar);
         let field2 = Field::unnamed(DataType::Int32);
-        let field3 = Field::unnamed(DataType::Int32);
-        let field4 = Field::unnamed(DataType::Int64);
-        let input_schema = Schema::new(vec![field1, field2, field3, field4]);
+        let field3 = Field::unnamed(DataType::Int64);
+        let input_schema = Schema::new(vec![field1, field2, field3]);
 
-        let agg_call_1 = AggCall::from_pretty("(min:varchar $0:varchar)"); // min(a)
-        let agg_call_2 = AggCall::from_pretty("(max:int4 $1:int4)"); // max(b)
-        let agg1 = build_append_only(&agg_call_1).unwrap();
-        let agg2 = build_append_only(&agg_call_2).unwrap();
+        let agg_call = AggCall::from_pretty("(max:varchar $0:varchar)"); // max(a)
+        let agg = build_append_only(&agg_call).unwrap();
         let group_key = None;
 
-        let (mut table_1, mapping_1) = create_mem_state_table(
+        let (mut table, mapping) = create_mem_state_table(
             &input_schema,
-            vec![0, 3],
+            vec![1, 2, 0],
             vec![
-                OrderType::ascending(), // for AggKind::Min
+                OrderType::descending(), // b DESC as the surrogate for max(a)
                 OrderType::ascending(),
             ],
         )
         .await;
-        let (mut table_2, mapping_2) = create_mem_state_table(
-            &input_schema,
-            vec![1, 3],
-            vec![
+
+        let order_columns = vec![
+            ColumnOrder::new(1, OrderType::descending()), // b DESC
+            ColumnOrder::new(2, OrderType::ascending()),  // _row_id
+        ];
+        let mut state = MaterializedInputState::new(
+            PbAggNodeVersion::Max,
+            &agg_call,
+            &PkIndices::new(), // unused
+            &order_columns,
+            true, // order by the surrogate key instead of the agg value
+            &mapping,
+            CacheCapacity::Rows(usize::MAX),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
+            &input_schema,
+        )
+        .unwrap();
+
+        let mut epoch = EpochPair::new_test_epoch(test_epoch(1));
+        table.init_epoch(epoch);
+
+        {
+            let chunk = create_chunk(
+                " T      i I
+                + apple  1 101
+                + cherry 3 102
+                + banana 2 103",
+                &mut table,
+                &mapping,
+            );
+
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res, Some("cherry".into()));
+        }
+
+        {
+            let chunk = create_chunk(
+                " T      i I
+                - cherry 3 102
+                + durian 4 104",
+                &mut table,
+                &mapping,
+            );
+
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res, Some("durian".into()));
+        }
+
+        {
+            // test recovery (cold start)
+            let mut state = MaterializedInputState::new(
+                PbAggNodeVersion::Max,
+                &agg_call,
+                &PkIndices::new(), // unused
+                &order_columns,
+                true,
+                &mapping,
+                CacheCapacity::Rows(usize::MAX),
+                None,
+                None,
+                None,
+                MetricsInfo::for_test(),
+                &input_schema,
+            )
+            .unwrap();
+
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res, Some("durian".into()));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_extreme_agg_state_with_hidden_input() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: varchar, b: int32, c: int32, _row_id: int64)
+
+        let field1 = Field::unnamed(DataType::Varchar);
+        let field2 = Field::unnamed(DataType::Int32);
+        let field3 = Field::unnamed(DataType::Int32);
+        let field4 = Field::unnamed(DataType::Int64);
+        let input_schema = Schema::new(vec![field1, field2, field3, field4]);
+
+        let agg_call_1 = AggCall::from_pretty("(min:varchar $0:varchar)"); // min(a)
+        let agg_call_2 = AggCall::from_pretty("(max:int4 $1:int4)"); // max(b)
+        let agg1 = build_append_only(&agg_call_1).unwrap();
+        let agg2 = build_append_only(&agg_call_2).unwrap();
+        let group_key = None;
+
+        let (mut table_1, mapping_1) = create_mem_state_table(
+            &input_schema,
+            vec![0, 3],
+            vec![
+                OrderType::ascending(), // for AggKind::Min
+                OrderType::ascending(),
+            ],
+        )
+        .await;
+        let (mut table_2, mapping_2) = create_mem_state_table(
+            &input_schema,
+            vec![1, 3],
+            vec![
                 OrderType::descending(), // for AggKind::Max
                 OrderType::ascending(),
             ],
@@ -595,8 +1323,13 @@ mod tests {
             &agg_call_1,
             &PkIndices::new(), // unused
             &order_columns_1,
+            false,
             &mapping_1,
-            usize::MAX,
+            CacheCapacity::Rows(usize::MAX),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
             &input_schema,
         )
         .unwrap();
@@ -610,8 +1343,13 @@ mod tests {
             &agg_call_2,
             &PkIndices::new(), // unused
             &order_columns_2,
+            false,
             &mapping_2,
-            usize::MAX,
+            CacheCapacity::Rows(usize::MAX),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
             &input_schema,
         )
         .unwrap();
@@ -663,6 +1401,152 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_prefetch_warms_cache() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: varchar, b: int32, c: int32, _row_id: int64)
+
+        let field1 = Field::unnamed(DataType::Varchar);
+        let field2 = Field::unnamed(DataType::Int32);
+        let field3 = Field::unnamed(DataType::Int32);
+        let field4 = Field::unnamed(DataType::Int64);
+        let input_schema = Schema::new(vec![field1, field2, field3, field4]);
+
+        let agg_call = AggCall::from_pretty("(min:int4 $2:int4)"); // min(c)
+        let agg = build_append_only(&agg_call).unwrap();
+        let group_key = None;
+
+        let (mut table, mapping) = create_mem_state_table(
+            &input_schema,
+            vec![2, 3],
+            vec![OrderType::ascending(), OrderType::ascending()],
+        )
+        .await;
+
+        let order_columns = vec![
+            ColumnOrder::new(2, OrderType::ascending()), // c ASC for AggKind::Min
+            ColumnOrder::new(3, OrderType::ascending()), // _row_id
+        ];
+
+        let mut epoch = EpochPair::new_test_epoch(test_epoch(1));
+        table.init_epoch(epoch);
+        create_chunk(
+            " T i i I
+            + a 1 8 123
+            + b 5 2 128",
+            &mut table,
+            &mapping,
+        );
+        epoch.inc_for_test();
+        table.commit(epoch).await.unwrap();
+
+        // Simulate recovery: a fresh state starts with a cold cache; prefetching warms
+        // it before the first output is requested.
+        let mut state = MaterializedInputState::new(
+            PbAggNodeVersion::Max,
+            &agg_call,
+            &PkIndices::new(), // unused
+            &order_columns,
+            false,
+            &mapping,
+            CacheCapacity::Rows(usize::MAX),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
+            &input_schema,
+        )
+        .unwrap();
+        assert!(!state.cache_is_synced());
+
+        state.prefetch(&table, group_key.as_ref()).await?;
+        assert!(state.cache_is_synced());
+
+        // Prefetching again is a no-op.
+        state.prefetch(&table, group_key.as_ref()).await?;
+        assert!(state.cache_is_synced());
+
+        let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+        assert_eq!(res, Some(2i32.into()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_get_outputs_multiple_groups() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: varchar, b: int32, c: int32, _row_id: int64)
+
+        let field1 = Field::unnamed(DataType::Varchar);
+        let field2 = Field::unnamed(DataType::Int32);
+        let field3 = Field::unnamed(DataType::Int32);
+        let field4 = Field::unnamed(DataType::Int64);
+        let input_schema = Schema::new(vec![field1, field2, field3, field4]);
+
+        let agg_call = AggCall::from_pretty("(max:int4 $1:int4)"); // max(b)
+        let agg = build_append_only(&agg_call).unwrap();
+
+        let (mut table, mapping) = create_mem_state_table(
+            &input_schema,
+            vec![2, 1, 3],
+            vec![
+                OrderType::ascending(),  // c ASC
+                OrderType::descending(), // b DESC for AggKind::Max
+                OrderType::ascending(),  // _row_id ASC
+            ],
+        )
+        .await;
+
+        let order_columns = vec![
+            ColumnOrder::new(1, OrderType::descending()), // b DESC for AggKind::Max
+            ColumnOrder::new(3, OrderType::ascending()),  // _row_id
+        ];
+        let state = MaterializedInputState::new(
+            PbAggNodeVersion::Max,
+            &agg_call,
+            &PkIndices::new(), // unused
+            &order_columns,
+            false,
+            &mapping,
+            CacheCapacity::Rows(usize::MAX),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
+            &input_schema,
+        )
+        .unwrap();
+
+        let mut epoch = EpochPair::new_test_epoch(test_epoch(1));
+        table.init_epoch(epoch);
+
+        create_chunk(
+            " T i i I
+            + a 1 8 123
+            + b 5 8 128
+            + c 7 3 130",
+            &mut table,
+            &mapping,
+        );
+        epoch.inc_for_test();
+        table.commit(epoch).await.unwrap();
+
+        // Outputs for both groups are fetched in one pass.
+        let group_keys = vec![
+            GroupKey::new(OwnedRow::new(vec![Some(8.into())]), None),
+            GroupKey::new(OwnedRow::new(vec![Some(3.into())]), None),
+        ];
+        let outputs = state.get_outputs(&table, &group_keys, &agg).await?;
+        assert_eq!(outputs, vec![Some(5i32.into()), Some(7i32.into())]);
+
+        // A group without any rows yields NULL.
+        let group_keys = vec![GroupKey::new(OwnedRow::new(vec![Some(42.into())]), None)];
+        let outputs = state.get_outputs(&table, &group_keys, &agg).await?;
+        assert_eq!(outputs, vec![None]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_extreme_agg_state_grouped() -> StreamExecutorResult<()> {
         // Assumption of input schema:
@@ -698,8 +1582,13 @@ mod tests {
             &agg_call,
             &PkIndices::new(), // unused
             &order_columns,
+            false,
             &mapping,
-            usize::MAX,
+            CacheCapacity::Rows(usize::MAX),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
             &input_schema,
         )
         .unwrap();
@@ -751,8 +1640,13 @@ mod tests {
                 &agg_call,
                 &PkIndices::new(), // unused
                 &order_columns,
+                false,
                 &mapping,
-                usize::MAX,
+                CacheCapacity::Rows(usize::MAX),
+                None,
+                None,
+                None,
+                MetricsInfo::for_test(),
                 &input_schema,
             )
             .unwrap();
@@ -799,8 +1693,13 @@ mod tests {
             &agg_call,
             &PkIndices::new(), // unused
             &order_columns,
+            false,
             &mapping,
-            1024,
+            CacheCapacity::Rows(1024),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
             &input_schema,
         )
         .unwrap();
@@ -903,8 +1802,13 @@ mod tests {
             &agg_call,
             &PkIndices::new(), // unused
             &order_columns,
+            false,
             &mapping,
-            3, // cache capacity = 3 for easy testing
+            CacheCapacity::Rows(3), // cache capacity = 3 for easy testing
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
             &input_schema,
         )
         .unwrap();
@@ -1016,8 +1920,13 @@ mod tests {
             &agg_call,
             &PkIndices::new(), // unused
             &order_columns,
+            false,
             &mapping,
-            usize::MAX,
+            CacheCapacity::Rows(usize::MAX),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
             &input_schema,
         )
         .unwrap();
@@ -1065,57 +1974,71 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_array_agg_state() -> StreamExecutorResult<()> {
+    async fn test_string_agg_state_null_delimiter() -> StreamExecutorResult<()> {
         // Assumption of input schema:
-        // (a: varchar, b: int32, c: int32, _row_id: int64)
+        // (a: varchar, _delim: varchar, b: int32, c: int32, _row_id: int64)
         // where `a` is the column to aggregate
+        //
+        // NULL delimiters concatenate directly (like an empty string), and the
+        // delimiter of the first emitted value is unused, matching Postgres. The
+        // replay through `func.update` must keep this stable when a retraction
+        // changes which row comes first.
 
-        let field1 = Field::unnamed(DataType::Varchar);
-        let field2 = Field::unnamed(DataType::Int32);
-        let field3 = Field::unnamed(DataType::Int32);
-        let field4 = Field::unnamed(DataType::Int64);
-        let input_schema = Schema::new(vec![field1, field2, field3, field4]);
+        let input_schema = Schema::new(vec![
+            Field::unnamed(DataType::Varchar),
+            Field::unnamed(DataType::Varchar),
+            Field::unnamed(DataType::Int32),
+            Field::unnamed(DataType::Int32),
+            Field::unnamed(DataType::Int64),
+        ]);
 
-        let agg_call = AggCall::from_pretty("(array_agg:int4[] $1:int4 orderby $2:asc $0:desc)");
+        let agg_call =
+            AggCall::from_pretty("(string_agg:varchar $0:varchar $1:varchar orderby $2:asc)");
         let agg = build_append_only(&agg_call).unwrap();
         let group_key = None;
 
         let (mut table, mapping) = create_mem_state_table(
             &input_schema,
-            vec![2, 0, 3, 1],
+            vec![2, 4, 0, 1],
             vec![
-                OrderType::ascending(),  // c ASC
-                OrderType::descending(), // a DESC
-                OrderType::ascending(),  // _row_id ASC
+                OrderType::ascending(), // b ASC
+                OrderType::ascending(), // _row_id ASC
             ],
         )
         .await;
 
         let order_columns = vec![
-            ColumnOrder::new(2, OrderType::ascending()),  // c ASC
-            ColumnOrder::new(0, OrderType::descending()), // a DESC
-            ColumnOrder::new(3, OrderType::ascending()),  // _row_id ASC
+            ColumnOrder::new(2, OrderType::ascending()), // b ASC
+            ColumnOrder::new(4, OrderType::ascending()), // _row_id ASC
         ];
         let mut state = MaterializedInputState::new(
             PbAggNodeVersion::Max,
             &agg_call,
             &PkIndices::new(), // unused
             &order_columns,
+            false,
             &mapping,
-            usize::MAX,
+            CacheCapacity::Rows(usize::MAX),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
             &input_schema,
         )
         .unwrap();
 
         let mut epoch = EpochPair::new_test_epoch(test_epoch(1));
         table.init_epoch(epoch);
+
         {
+            // `a` and `c` carry NULL delimiters; `a`'s is unused anyway as it comes
+            // first.
             let chunk = create_chunk(
-                " T i i I
-                + a 1 8 123
-                + b 5 2 128
-                - b 5 2 128
-                + c 2 3 130",
+                " T T i i I
+                + a . 1 8 123
+                + b , 2 2 124
+                + c . 3 3 125
+                + d _ 4 3 126",
                 &mut table,
                 &mapping,
             );
@@ -1125,14 +2048,16 @@ mod tests {
             table.commit(epoch).await.unwrap();
 
             let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
-            assert_eq!(res.unwrap().as_list(), &ListValue::from_iter([2, 1]));
+            assert_eq!(res, Some("a,bc_d".into()));
         }
 
         {
+            // Retracting `a` makes `b` the first value, so its `,` delimiter becomes
+            // unused; the appended `e` has a NULL delimiter.
             let chunk = create_chunk(
-                " T i i I
-                + d 0 8 134
-                + e 2 2 137",
+                " T T i i I
+                - a . 1 8 123
+                + e . 5 2 127",
                 &mut table,
                 &mapping,
             );
@@ -1142,9 +2067,640 @@ mod tests {
             table.commit(epoch).await.unwrap();
 
             let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
-            assert_eq!(res.unwrap().as_list(), &ListValue::from_iter([2, 2, 0, 1]));
+            assert_eq!(res, Some("bc_de".into()));
         }
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_string_agg_state_bounded_cache() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: varchar, _delim: varchar, b: int32, c: int32, _row_id: int64)
+        // where `a` is the column to aggregate
+
+        let input_schema = Schema::new(vec![
+            Field::unnamed(DataType::Varchar),
+            Field::unnamed(DataType::Varchar),
+            Field::unnamed(DataType::Int32),
+            Field::unnamed(DataType::Int32),
+            Field::unnamed(DataType::Int64),
+        ]);
+
+        let agg_call =
+            AggCall::from_pretty("(string_agg:varchar $0:varchar $1:varchar orderby $2:asc)");
+        let agg = build_append_only(&agg_call).unwrap();
+        let group_key = None;
+
+        let (mut table, mapping) = create_mem_state_table(
+            &input_schema,
+            vec![2, 4, 0, 1],
+            vec![
+                OrderType::ascending(), // b ASC
+                OrderType::ascending(), // _row_id ASC
+            ],
+        )
+        .await;
+
+        let order_columns = vec![
+            ColumnOrder::new(2, OrderType::ascending()), // b ASC
+            ColumnOrder::new(4, OrderType::ascending()), // _row_id ASC
+        ];
+        // Bound the cache to 2 rows so that it holds only a prefix of the group and the
+        // spilled suffix is folded in from the state table.
+        let mut state = MaterializedInputState::new(
+            PbAggNodeVersion::Max,
+            &agg_call,
+            &PkIndices::new(), // unused
+            &order_columns,
+            false,
+            &mapping,
+            CacheCapacity::Rows(2),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
+            &input_schema,
+        )
+        .unwrap();
+
+        let mut epoch = EpochPair::new_test_epoch(test_epoch(1));
+        table.init_epoch(epoch);
+
+        {
+            let chunk = create_chunk(
+                " T T i i I
+                + a , 1 8 123
+                + b . 2 2 124
+                + c _ 3 3 125
+                + d / 4 3 126",
+                &mut table,
+                &mapping,
+            );
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            // `a` and `b` are cached, `c` and `d` come from the suffix scan.
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res, Some("a.b_c/d".into()));
+            assert!(state.cache_is_synced());
+        }
+
+        {
+            let chunk = create_chunk(
+                " T T i i I
+                - a , 1 8 123
+                + e + 5 2 127",
+                &mut table,
+                &mapping,
+            );
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            // Deleting from the cached prefix and inserting beyond the cache boundary
+            // keep the cache valid: the cached prefix shrinks to `b` and the rest is
+            // read from the state table.
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res, Some("b_c/d+e".into()));
+            assert!(state.cache_is_synced());
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_array_agg_state() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: varchar, b: int32, c: int32, _row_id: int64)
+        // where `a` is the column to aggregate
+
+        let field1 = Field::unnamed(DataType::Varchar);
+        let field2 = Field::unnamed(DataType::Int32);
+        let field3 = Field::unnamed(DataType::Int32);
+        let field4 = Field::unnamed(DataType::Int64);
+        let input_schema = Schema::new(vec![field1, field2, field3, field4]);
+
+        let agg_call = AggCall::from_pretty("(array_agg:int4[] $1:int4 orderby $2:asc $0:desc)");
+        let agg = build_append_only(&agg_call).unwrap();
+        let group_key = None;
+
+        let (mut table, mapping) = create_mem_state_table(
+            &input_schema,
+            vec![2, 0, 3, 1],
+            vec![
+                OrderType::ascending(),  // c ASC
+                OrderType::descending(), // a DESC
+                OrderType::ascending(),  // _row_id ASC
+            ],
+        )
+        .await;
+
+        let order_columns = vec![
+            ColumnOrder::new(2, OrderType::ascending()),  // c ASC
+            ColumnOrder::new(0, OrderType::descending()), // a DESC
+            ColumnOrder::new(3, OrderType::ascending()),  // _row_id ASC
+        ];
+        let mut state = MaterializedInputState::new(
+            PbAggNodeVersion::Max,
+            &agg_call,
+            &PkIndices::new(), // unused
+            &order_columns,
+            false,
+            &mapping,
+            CacheCapacity::Rows(usize::MAX),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
+            &input_schema,
+        )
+        .unwrap();
+
+        let mut epoch = EpochPair::new_test_epoch(test_epoch(1));
+        table.init_epoch(epoch);
+        {
+            let chunk = create_chunk(
+                " T i i I
+                + a 1 8 123
+                + b 5 2 128
+                - b 5 2 128
+                + c 2 3 130",
+                &mut table,
+                &mapping,
+            );
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res.unwrap().as_list(), &ListValue::from_iter([2, 1]));
+        }
+
+        {
+            let chunk = create_chunk(
+                " T i i I
+                + d 0 8 134
+                + e 2 2 137",
+                &mut table,
+                &mapping,
+            );
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res.unwrap().as_list(), &ListValue::from_iter([2, 2, 0, 1]));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_array_agg_struct_state() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: struct<x int4, y varchar>, b: int32, _row_id: int64)
+        // where `a` is the column to aggregate, ordered by `b`.
+
+        let struct_type = DataType::Struct(StructType::new(vec![
+            ("x", DataType::Int32),
+            ("y", DataType::Varchar),
+        ]));
+        let input_schema = Schema::new(vec![
+            Field::unnamed(struct_type.clone()),
+            Field::unnamed(DataType::Int32),
+            Field::unnamed(DataType::Int64),
+        ]);
+        let data_types = input_schema.data_types();
+
+        let agg_call = AggCall::from_pretty(
+            "(array_agg:struct<x_int4,y_varchar>[] $0:struct<x_int4,y_varchar> orderby $1:asc $2:asc)",
+        );
+        let agg = build_append_only(&agg_call).unwrap();
+        let group_key = None;
+
+        let (mut table, mapping) = create_mem_state_table(
+            &input_schema,
+            vec![1, 2, 0],
+            vec![
+                OrderType::ascending(), // b ASC
+                OrderType::ascending(), // _row_id ASC
+            ],
+        )
+        .await;
+
+        let order_columns = vec![
+            ColumnOrder::new(1, OrderType::ascending()), // b ASC
+            ColumnOrder::new(2, OrderType::ascending()), // _row_id ASC
+        ];
+        let make_state = || {
+            MaterializedInputState::new(
+                PbAggNodeVersion::Max,
+                &agg_call,
+                &PkIndices::new(), // unused
+                &order_columns,
+                false,
+                &mapping,
+                CacheCapacity::Rows(usize::MAX),
+                None,
+                None,
+                None,
+                MetricsInfo::for_test(),
+                &input_schema,
+            )
+            .unwrap()
+        };
+        let mut state = make_state();
+
+        let struct_value = |x: i32, y: &str| {
+            ScalarImpl::Struct(StructValue::new(vec![
+                Some(ScalarImpl::Int32(x)),
+                Some(ScalarImpl::Utf8(y.into())),
+            ]))
+        };
+        let input_row = |x: i32, y: &str, b: i32, row_id: i64| {
+            OwnedRow::new(vec![
+                Some(struct_value(x, y)),
+                Some(ScalarImpl::Int32(b)),
+                Some(ScalarImpl::Int64(row_id)),
+            ])
+        };
+
+        let mut epoch = EpochPair::new_test_epoch(test_epoch(1));
+        table.init_epoch(epoch);
+        {
+            let chunk = StreamChunk::from_rows(
+                &[
+                    (Op::Insert, input_row(1, "a", 8, 123)),
+                    (Op::Insert, input_row(2, "b", 2, 124)),
+                    (Op::Insert, input_row(3, "c", 3, 125)),
+                    (Op::Delete, input_row(3, "c", 3, 125)),
+                ],
+                &data_types,
+            );
+            table.write_chunk(chunk.project(mapping.upstream_columns()));
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(
+                res.unwrap().as_list(),
+                &ListValue::from_datum_iter(
+                    &struct_type,
+                    [Some(struct_value(2, "b")), Some(struct_value(1, "a"))]
+                )
+            );
+        }
+
+        {
+            // A fresh state with an empty cache, as after a cold-start recovery, must
+            // rebuild the same output from the state table.
+            let mut state = make_state();
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(
+                res.unwrap().as_list(),
+                &ListValue::from_datum_iter(
+                    &struct_type,
+                    [Some(struct_value(2, "b")), Some(struct_value(1, "a"))]
+                )
+            );
+        }
+
+        {
+            let chunk = StreamChunk::from_rows(
+                &[
+                    (Op::Delete, input_row(1, "a", 8, 123)),
+                    (Op::Insert, input_row(4, "d", 5, 126)),
+                ],
+                &data_types,
+            );
+            table.write_chunk(chunk.project(mapping.upstream_columns()));
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(
+                res.unwrap().as_list(),
+                &ListValue::from_datum_iter(
+                    &struct_type,
+                    [Some(struct_value(2, "b")), Some(struct_value(4, "d"))]
+                )
+            );
+        }
+
+        {
+            // Recover again after the delete to check the ordering is preserved.
+            let mut state = make_state();
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(
+                res.unwrap().as_list(),
+                &ListValue::from_datum_iter(
+                    &struct_type,
+                    [Some(struct_value(2, "b")), Some(struct_value(4, "d"))]
+                )
+            );
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_array_agg_distinct_state_legacy_order() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: varchar, b: int32, c: int32, _row_id: int64)
+        // where `b` is the column to aggregate
+        //
+        // This test covers the order columns generated for plans before
+        // `PbAggNodeVersion::Issue13465`: for `array_agg(distinct b order by c)`, the
+        // order key must be `(c ASC, b ASC, _row_id ASC)`, with the primary key as
+        // tie-breaker so that rows sharing `(c, b)` do not collide in the state table.
+
+        let field1 = Field::unnamed(DataType::Varchar);
+        let field2 = Field::unnamed(DataType::Int32);
+        let field3 = Field::unnamed(DataType::Int32);
+        let field4 = Field::unnamed(DataType::Int64);
+        let input_schema = Schema::new(vec![field1, field2, field3, field4]);
+
+        let agg_call = AggCall::from_pretty("(array_agg:int4[] $1:int4 distinct orderby $2:asc)");
+        let agg = build_append_only(&agg_call).unwrap();
+        let group_key = None;
+
+        let (mut table, mapping) = create_mem_state_table(
+            &input_schema,
+            vec![2, 1, 3],
+            vec![
+                OrderType::ascending(), // c ASC
+                OrderType::ascending(), // b ASC (distinct key)
+                OrderType::ascending(), // _row_id ASC (pk tie-breaker)
+            ],
+        )
+        .await;
+
+        let mut state = MaterializedInputState::new(
+            PbAggNodeVersion::Issue12140, // before `Issue13465`
+            &agg_call,
+            &vec![3], // _row_id
+            &[], // unused
+            false,
+            &mapping,
+            CacheCapacity::Rows(usize::MAX),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
+            &input_schema,
+        )
+        .unwrap();
+
+        let mut epoch = EpochPair::new_test_epoch(test_epoch(1));
+        table.init_epoch(epoch);
+        {
+            let chunk = create_chunk(
+                " T i i I
+                + x 1 1 101
+                + x 1 1 102
+                + x 2 1 103
+                - x 1 1 101
+                + x 0 2 104",
+                &mut table,
+                &mapping,
+            );
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            // The two rows with `(c, b) = (1, 1)` are distinguished by `_row_id`, so
+            // deleting one of them must keep the other.
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res.unwrap().as_list(), &ListValue::from_iter([1, 2, 0]));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_array_agg_distinct_state_partial_cache() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: varchar, b: int32, c: int32, _row_id: int64)
+        // where `b` is the column to aggregate
+
+        let field1 = Field::unnamed(DataType::Varchar);
+        let field2 = Field::unnamed(DataType::Int32);
+        let field3 = Field::unnamed(DataType::Int32);
+        let field4 = Field::unnamed(DataType::Int64);
+        let input_schema = Schema::new(vec![field1, field2, field3, field4]);
+
+        let agg_call = AggCall::from_pretty("(array_agg:int4[] $1:int4 distinct orderby $1:asc)");
+        let agg = build_append_only(&agg_call).unwrap();
+        let group_key = None;
+
+        let (mut table, mapping) = create_mem_state_table(
+            &input_schema,
+            vec![1, 3],
+            vec![
+                OrderType::ascending(), // b ASC (distinct key)
+                OrderType::ascending(), // _row_id ASC (pk tie-breaker)
+            ],
+        )
+        .await;
+
+        // Bound the cache to 3 rows so that it ends amid the duplicates of `b = 2`:
+        // the dedup must carry the last cached value into the suffix scan, or the
+        // duplicate beyond the cache boundary would be double-counted.
+        let mut state = MaterializedInputState::new(
+            PbAggNodeVersion::Issue12140, // before `Issue13465`
+            &agg_call,
+            &vec![3], // _row_id
+            &[], // unused
+            false,
+            &mapping,
+            CacheCapacity::Rows(3),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
+            &input_schema,
+        )
+        .unwrap();
+
+        let mut epoch = EpochPair::new_test_epoch(test_epoch(1));
+        table.init_epoch(epoch);
+
+        {
+            let chunk = create_chunk(
+                " T i i I
+                + x 1 1 101
+                + x 1 1 102
+                + x 2 1 103
+                + x 2 1 104
+                + x 3 1 105",
+                &mut table,
+                &mapping,
+            );
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res.unwrap().as_list(), &ListValue::from_iter([1, 2, 3]));
+        }
+
+        {
+            // Deleting one of the duplicates must not drop the value from the output.
+            let chunk = create_chunk(
+                " T i i I
+                - x 2 1 103",
+                &mut table,
+                &mapping,
+            );
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res.unwrap().as_list(), &ListValue::from_iter([1, 2, 3]));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_first_value_ignore_nulls() -> StreamExecutorResult<()> {
+        // Assumption of input schema:
+        // (a: int32, b: int32, _row_id: int64)
+        // where `a` is the column to aggregate
+
+        let field1 = Field::unnamed(DataType::Int32);
+        let field2 = Field::unnamed(DataType::Int32);
+        let field3 = Field::unnamed(DataType::Int64);
+        let input_schema = Schema::new(vec![field1, field2, field3]);
+
+        let agg_call =
+            AggCall::from_pretty("(first_value:int4 $0:int4 ignore_nulls orderby $1:asc)");
+        let agg = build_append_only(&agg_call).unwrap();
+        let group_key = None;
+
+        let (mut table, mapping) = create_mem_state_table(
+            &input_schema,
+            vec![1, 2, 0],
+            vec![
+                OrderType::ascending(), // b ASC
+                OrderType::ascending(), // _row_id ASC
+            ],
+        )
+        .await;
+
+        let order_columns = vec![
+            ColumnOrder::new(1, OrderType::ascending()), // b ASC
+            ColumnOrder::new(2, OrderType::ascending()), // _row_id ASC
+        ];
+        // Bound the cache to 2 rows so that it may hold only null entries, to cover
+        // the fall-back scan of the state table.
+        let mut state = MaterializedInputState::new(
+            PbAggNodeVersion::Max,
+            &agg_call,
+            &PkIndices::new(), // unused
+            &order_columns,
+            false,
+            &mapping,
+            CacheCapacity::Rows(2),
+            None,
+            None,
+            None,
+            MetricsInfo::for_test(),
+            &input_schema,
+        )
+        .unwrap();
+
+        let mut epoch = EpochPair::new_test_epoch(test_epoch(1));
+        table.init_epoch(epoch);
+
+        {
+            let chunk = create_chunk(
+                " i i I
+                + . 1 101
+                + . 2 102
+                + 5 3 103
+                + 4 4 104",
+                &mut table,
+                &mapping,
+            );
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            // All cached entries are null, so the first non-null value comes from the
+            // state table scan.
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res, Some(5.into()));
+
+            // Without `IGNORE NULLS`, the first value by order is null.
+            let agg_call = AggCall::from_pretty("(first_value:int4 $0:int4 orderby $1:asc)");
+            let agg = build_append_only(&agg_call).unwrap();
+            let mut state = MaterializedInputState::new(
+                PbAggNodeVersion::Max,
+                &agg_call,
+                &PkIndices::new(), // unused
+                &order_columns,
+                false,
+                &mapping,
+                CacheCapacity::Rows(1024),
+                None,
+                None,
+                None,
+                MetricsInfo::for_test(),
+                &input_schema,
+            )
+            .unwrap();
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res, None);
+        }
+
+        {
+            let chunk = create_chunk(
+                " i i I
+                - . 1 101
+                + 0 0 100",
+                &mut table,
+                &mapping,
+            );
+            state.apply_chunk(&chunk)?;
+
+            epoch.inc_for_test();
+            table.commit(epoch).await.unwrap();
+
+            // Now the cache holds a non-null entry at the front and serves the output.
+            let res = state.get_output(&table, group_key.as_ref(), &agg).await?;
+            assert_eq!(res, Some(0.into()));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_adaptive_chunk_size() {
+        // Fixed-width arguments keep the default size; variable-width ones shrink it.
+        assert_eq!(adaptive_chunk_size(&[DataType::Int64]), CHUNK_SIZE);
+        assert_eq!(
+            adaptive_chunk_size(&[DataType::Varchar, DataType::Int32]),
+            CHUNK_SIZE / 4
+        );
+        assert_eq!(
+            adaptive_chunk_size(&[DataType::List(Box::new(DataType::Int32))]),
+            CHUNK_SIZE / 4
+        );
+    }
 }
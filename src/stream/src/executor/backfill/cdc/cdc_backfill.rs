@@ -210,7 +210,12 @@ impl<S: StateStore> CdcBackfillExecutor<S> {
                         break;
                     }
                     Message::Chunk(ref chunk) => {
-                        last_binlog_offset = get_cdc_chunk_last_offset(&offset_parse_func, chunk)?;
+                        // a chunk without a position (e.g. a heartbeat) keeps the
+                        // last known offset
+                        if let Some(offset) = get_cdc_chunk_last_offset(&offset_parse_func, chunk)?
+                        {
+                            last_binlog_offset = Some(offset);
+                        }
                     }
                     Message::Watermark(_) => {
                         // Ignore watermark
@@ -274,11 +279,14 @@ impl<S: StateStore> CdcBackfillExecutor<S> {
                                                 chunk.cardinality() as u64;
 
                                             // record the consumed binlog offset that will be
-                                            // persisted later
-                                            consumed_binlog_offset = get_cdc_chunk_last_offset(
+                                            // persisted later; a chunk without a position
+                                            // keeps the previously consumed offset
+                                            if let Some(offset) = get_cdc_chunk_last_offset(
                                                 &offset_parse_func,
                                                 &chunk,
-                                            )?;
+                                            )? {
+                                                consumed_binlog_offset = Some(offset);
+                                            }
                                             yield Message::Chunk(mapping_chunk(
                                                 mark_cdc_chunk(
                                                     &offset_parse_func,
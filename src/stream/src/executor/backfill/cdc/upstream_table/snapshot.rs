@@ -96,6 +96,7 @@ impl UpstreamTableRead for UpstreamTableReader<ExternalStorageTable> {
             self.inner.schema_table_name(),
             args.current_pos,
             primary_keys,
+            self.inner.pk_order_types().to_vec(),
         );
 
         pin_mut!(row_stream);
@@ -414,9 +414,12 @@ fn mark_cdc_chunk_inner(
         let offset_datum = row.datum_at(offset_col_idx).unwrap();
         let event_offset = (*offset_parse_func)(offset_datum.into_utf8())?;
         let visible = {
-            // filter changelog events with binlog range
-            let in_binlog_range = if let Some(binlog_low) = &last_cdc_offset {
-                binlog_low <= &event_offset
+            // filter changelog events with binlog range; an event without a position
+            // (e.g. a heartbeat offset) cannot be compared and is kept
+            let in_binlog_range = if let (Some(binlog_low), Some(event_offset)) =
+                (&last_cdc_offset, &event_offset)
+            {
+                binlog_low <= event_offset
             } else {
                 true
             };
@@ -621,15 +624,18 @@ pub(crate) fn get_new_pos(chunk: &StreamChunk, pk_in_output_indices: &[usize]) -
         .into_owned_row()
 }
 
+/// Returns `None` both for a NULL offset column and for an offset that carries no
+/// position (e.g. a heartbeat offset); callers keep their last known offset then.
 pub(crate) fn get_cdc_chunk_last_offset(
     offset_parse_func: &CdcOffsetParseFunc,
     chunk: &StreamChunk,
 ) -> StreamExecutorResult<Option<CdcOffset>> {
     let row = chunk.rows().last().unwrap().1;
     let offset_col = row.iter().last().unwrap();
-    let output =
-        offset_col.map(|scalar| Ok::<_, ConnectorError>((*offset_parse_func)(scalar.into_utf8()))?);
-    output.transpose().map_err(|e| e.into())
+    match offset_col {
+        Some(scalar) => (*offset_parse_func)(scalar.into_utf8()).map_err(|e| e.into()),
+        None => Ok(None),
+    }
 }
 
 // NOTE(kwannoel): ["None" ..] encoding should be appropriate to mark
@@ -50,7 +50,7 @@ impl<K: Hash + Eq + EstimateSize> DedupCache<K> {
 
     /// Evict the inner LRU cache according to the watermark epoch.
     pub fn evict(&mut self) {
-        self.inner.evict()
+        self.inner.evict();
     }
 
     pub fn update_epoch(&mut self, epoch: u64) {
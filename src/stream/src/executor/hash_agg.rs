@@ -30,6 +30,7 @@ use risingwave_common::util::epoch::EpochPair;
 use risingwave_common::util::iter_util::ZipEqFast;
 use risingwave_expr::aggregate::{build_retractable, AggCall, BoxedAggregateFunction};
 use risingwave_pb::stream_plan::PbAggNodeVersion;
+use risingwave_storage::store::PrefetchOptions;
 use risingwave_storage::StateStore;
 
 use super::agg_common::{AggExecutorArgs, HashAggExecutorExtraArgs};
@@ -128,6 +129,14 @@ struct ExecutorInner<K: HashKey, S: StateStore> {
     /// State cache size for extreme agg.
     extreme_cache_size: usize,
 
+    /// Limit in bytes of a serialized extreme agg cache key. Entries with larger keys
+    /// are not cached.
+    extreme_cache_key_size_limit: Option<usize>,
+
+    /// Prefetch options for the cold-start sync scan of agg state tables. `None`
+    /// derives the options from the cache capacity.
+    state_sync_prefetch: Option<PrefetchOptions>,
+
     /// The maximum size of the chunk produced by executor at a time.
     chunk_size: usize,
 
@@ -231,6 +240,8 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                 distinct_dedup_tables: args.distinct_dedup_tables,
                 watermark_epoch: args.watermark_epoch,
                 extreme_cache_size: args.extreme_cache_size,
+                extreme_cache_key_size_limit: args.extreme_cache_key_size_limit,
+                state_sync_prefetch: args.state_sync_prefetch,
                 chunk_size: args.extra.chunk_size,
                 max_dirty_groups_heap_size: args.extra.max_dirty_groups_heap_size,
                 emit_on_window_close: args.extra.emit_on_window_close,
@@ -309,7 +320,10 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                                 &this.input_pk_indices,
                                 this.row_count_index,
                                 this.extreme_cache_size,
+                                this.extreme_cache_key_size_limit,
+                                this.state_sync_prefetch,
                                 &this.input_schema,
+                                &this.actor_ctx,
                             )
                             .await?;
                             Ok::<_, StreamExecutorError>((key.clone(), Box::new(agg_group)))
@@ -464,7 +478,10 @@ impl<K: HashKey, S: StateStore> HashAggExecutor<K, S> {
                         &this.input_pk_indices,
                         this.row_count_index,
                         this.extreme_cache_size,
+                        this.extreme_cache_key_size_limit,
+                        this.state_sync_prefetch,
                         &this.input_schema,
+                        &this.actor_ctx,
                     )?;
 
                     let change = agg_group
@@ -99,6 +99,8 @@ pub struct StreamingMetrics {
     pub agg_distinct_cached_entry_count: GenericGaugeVec<AtomicI64>,
     pub agg_dirty_groups_count: GenericGaugeVec<AtomicI64>,
     pub agg_dirty_groups_heap_size: GenericGaugeVec<AtomicI64>,
+    pub agg_state_cache_sync_count: LabelGuardedIntCounterVec<3>,
+    pub agg_state_cache_hit_count: LabelGuardedIntCounterVec<3>,
 
     // Streaming TopN
     pub group_top_n_cache_miss_count: GenericCounterVec<AtomicU64>,
@@ -177,6 +179,8 @@ pub struct StreamingMetrics {
     pub lru_runtime_loop_count: IntCounter,
     pub lru_watermark_step: IntGauge,
     pub lru_evicted_watermark_time_ms: LabelGuardedIntGaugeVec<3>,
+    pub lru_evicted_entry_count: LabelGuardedIntCounterVec<3>,
+    pub lru_evicted_bytes: LabelGuardedIntCounterVec<3>,
     pub jemalloc_allocated_bytes: IntGauge,
     pub jemalloc_active_bytes: IntGauge,
     pub jemalloc_resident_bytes: IntGauge,
@@ -549,6 +553,22 @@ impl StreamingMetrics {
         )
         .unwrap();
 
+        let agg_state_cache_sync_count = register_guarded_int_counter_vec_with_registry!(
+            "stream_agg_state_cache_sync_count",
+            "Materialized input agg state cache cold-start syncs from the state table",
+            &["table_id", "actor_id", "agg_kind"],
+            registry
+        )
+        .unwrap();
+
+        let agg_state_cache_hit_count = register_guarded_int_counter_vec_with_registry!(
+            "stream_agg_state_cache_hit_count",
+            "Materialized input agg state outputs served from the synced cache",
+            &["table_id", "actor_id", "agg_kind"],
+            registry
+        )
+        .unwrap();
+
         let group_top_n_cache_miss_count = register_int_counter_vec_with_registry!(
             "stream_group_top_n_cache_miss_count",
             "Group top n executor cache miss count",
@@ -953,6 +973,22 @@ impl StreamingMetrics {
         )
         .unwrap();
 
+        let lru_evicted_entry_count = register_guarded_int_counter_vec_with_registry!(
+            "lru_evicted_entry_count",
+            "Accumulated number of cache entries dropped by eviction passes",
+            &["table_id", "actor_id", "desc"],
+            registry
+        )
+        .unwrap();
+
+        let lru_evicted_bytes = register_guarded_int_counter_vec_with_registry!(
+            "lru_evicted_bytes",
+            "Accumulated estimated bytes freed by eviction passes",
+            &["table_id", "actor_id", "desc"],
+            registry
+        )
+        .unwrap();
+
         let jemalloc_allocated_bytes = register_int_gauge_with_registry!(
             "jemalloc_allocated_bytes",
             "The allocated memory jemalloc, got from jemalloc_ctl",
@@ -1102,6 +1138,8 @@ impl StreamingMetrics {
             agg_distinct_cached_entry_count,
             agg_dirty_groups_count,
             agg_dirty_groups_heap_size,
+            agg_state_cache_sync_count,
+            agg_state_cache_hit_count,
             group_top_n_cache_miss_count,
             group_top_n_total_query_cache_count,
             group_top_n_cached_entry_count,
@@ -1153,6 +1191,8 @@ impl StreamingMetrics {
             lru_runtime_loop_count,
             lru_watermark_step,
             lru_evicted_watermark_time_ms,
+            lru_evicted_entry_count,
+            lru_evicted_bytes,
             jemalloc_allocated_bytes,
             jemalloc_active_bytes,
             jemalloc_resident_bytes,
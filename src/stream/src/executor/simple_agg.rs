@@ -19,6 +19,7 @@ use risingwave_common::catalog::Schema;
 use risingwave_common::util::iter_util::ZipEqFast;
 use risingwave_expr::aggregate::{build_retractable, AggCall, BoxedAggregateFunction};
 use risingwave_pb::stream_plan::PbAggNodeVersion;
+use risingwave_storage::store::PrefetchOptions;
 use risingwave_storage::StateStore;
 
 use super::agg_common::{AggExecutorArgs, SimpleAggExecutorExtraArgs};
@@ -90,6 +91,14 @@ struct ExecutorInner<S: StateStore> {
 
     /// Extreme state cache size
     extreme_cache_size: usize,
+
+    /// Limit in bytes of a serialized extreme agg cache key. Entries with larger keys
+    /// are not cached.
+    extreme_cache_key_size_limit: Option<usize>,
+
+    /// Prefetch options for the cold-start sync scan of agg state tables. `None`
+    /// derives the options from the cache capacity.
+    state_sync_prefetch: Option<PrefetchOptions>,
 }
 
 impl<S: StateStore> ExecutorInner<S> {
@@ -136,6 +145,8 @@ impl<S: StateStore> SimpleAggExecutor<S> {
                 distinct_dedup_tables: args.distinct_dedup_tables,
                 watermark_epoch: args.watermark_epoch,
                 extreme_cache_size: args.extreme_cache_size,
+                extreme_cache_key_size_limit: args.extreme_cache_key_size_limit,
+                state_sync_prefetch: args.state_sync_prefetch,
             },
         })
     }
@@ -263,7 +274,10 @@ impl<S: StateStore> SimpleAggExecutor<S> {
                 &this.input_pk_indices,
                 this.row_count_index,
                 this.extreme_cache_size,
+                this.extreme_cache_key_size_limit,
+                this.state_sync_prefetch,
                 &this.input_schema,
+                &this.actor_ctx,
             )
             .await?,
             distinct_dedup,
@@ -484,6 +484,8 @@ pub mod agg_executor {
             info: info.clone(),
 
             extreme_cache_size,
+            extreme_cache_key_size_limit: None,
+            state_sync_prefetch: None,
 
             agg_calls,
             row_count_index,
@@ -551,6 +553,8 @@ pub mod agg_executor {
             info: info.clone(),
 
             extreme_cache_size: 1024,
+            extreme_cache_key_size_limit: None,
+            state_sync_prefetch: None,
 
             agg_calls,
             row_count_index,
@@ -102,6 +102,12 @@ impl ExecutorBuilder for HashAggExecutorBuilder {
                 info: params.info.clone(),
 
                 extreme_cache_size: params.env.config().developer.unsafe_extreme_cache_size,
+                extreme_cache_key_size_limit: params
+                    .env
+                    .config()
+                    .developer
+                    .unsafe_extreme_cache_key_size_limit,
+                state_sync_prefetch: None,
 
                 agg_calls,
                 row_count_index: node.get_row_count_index() as usize,
@@ -346,6 +346,23 @@ impl Cluster {
             .await
     }
 
+    /// Cordon a worker and reschedule all of its actors onto the remaining workers. The
+    /// worker is left cordoned afterwards.
+    #[cfg_or_panic(madsim)]
+    pub async fn drain_worker(&self, id: u32) -> Result<()> {
+        self.ctl
+            .spawn(async move {
+                risingwave_ctl::cmd_impl::scale::drain(
+                    &risingwave_ctl::common::CtlContext::default(),
+                    vec![id.to_string()],
+                    true,
+                )
+                .await
+            })
+            .await??;
+        Ok(())
+    }
+
     /// Reschedule with the given `plan`. Check the document of
     /// [`risingwave_ctl::cmd_impl::meta::reschedule`] for more details.
     pub async fn reschedule(&mut self, plan: impl Into<String>) -> Result<()> {
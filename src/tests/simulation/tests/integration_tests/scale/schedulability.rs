@@ -78,6 +78,68 @@ async fn test_cordon_normal() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_drain_worker() -> Result<()> {
+    let mut cluster = Cluster::start(Configuration::for_scale()).await?;
+    let mut session = cluster.start_session();
+
+    session.run("create table t (v int);").await?;
+
+    let mut workers: Vec<WorkerNode> = cluster
+        .get_cluster_info()
+        .await?
+        .worker_nodes
+        .into_iter()
+        .filter(|worker| {
+            worker.r#type() == WorkerType::ComputeNode
+                && worker.property.as_ref().unwrap().is_streaming
+        })
+        .collect();
+
+    let drained_worker = workers.pop().unwrap();
+
+    let drained_parallel_unit_ids: HashSet<_> = drained_worker
+        .parallel_units
+        .iter()
+        .map(|parallel_unit| parallel_unit.id as ParallelUnitId)
+        .collect();
+
+    let rest_parallel_unit_ids: HashSet<_> = workers
+        .iter()
+        .flat_map(|worker| {
+            worker
+                .parallel_units
+                .iter()
+                .map(|parallel_unit| parallel_unit.id as ParallelUnitId)
+        })
+        .collect();
+
+    cluster.drain_worker(drained_worker.id).await?;
+
+    // All existing actors must have been moved off the drained worker.
+    let fragments = cluster.locate_fragments([]).await?;
+
+    for fragment in fragments {
+        let (_, used) = fragment.parallel_unit_usage();
+
+        assert!(used.is_disjoint(&drained_parallel_unit_ids));
+        assert_eq!(used, rest_parallel_unit_ids);
+    }
+
+    // The worker is left cordoned, so new jobs must avoid it as well.
+    session.run("create table t2 (v int);").await?;
+
+    let fragments = cluster.locate_fragments([]).await?;
+
+    for fragment in fragments {
+        let (_, used) = fragment.parallel_unit_usage();
+
+        assert!(used.is_disjoint(&drained_parallel_unit_ids));
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_cordon_no_shuffle_failed() -> Result<()> {
     let mut cluster = Cluster::start(Configuration::for_scale()).await?;